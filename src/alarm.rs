@@ -0,0 +1,226 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Weekday};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/*
+    Alarms saved in the configuration file.
+
+    [[alarms]]
+    id = 1                          # the snoozes and the notifications. given if missing ( older versions )
+    time = "07:30"                  # in the time zone of the first clock
+    weekdays = ["mon", "tue"]       # sun, mon, ... sat. empty = every day
+    label = "Wake up"
+    enabled = true
+*/
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlarmDay
+{
+    Sun,
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+}
+
+impl AlarmDay
+{
+    pub const ALL: [AlarmDay; 7] = [
+        AlarmDay::Sun,
+        AlarmDay::Mon,
+        AlarmDay::Tue,
+        AlarmDay::Wed,
+        AlarmDay::Thu,
+        AlarmDay::Fri,
+        AlarmDay::Sat,
+    ];
+
+    pub fn from_weekday(weekday: Weekday) -> Self
+    {
+        Self::ALL[weekday.num_days_from_sunday() as usize]
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        match self
+        {
+            AlarmDay::Sun => "Sun",
+            AlarmDay::Mon => "Mon",
+            AlarmDay::Tue => "Tue",
+            AlarmDay::Wed => "Wed",
+            AlarmDay::Thu => "Thu",
+            AlarmDay::Fri => "Fri",
+            AlarmDay::Sat => "Sat",
+        }
+    }
+}
+
+// "7:30", "07:30", "07:30:00"
+pub fn parse_alarm_time(src: &str) -> Option<NaiveTime>
+{
+    let src = src.trim();
+
+    NaiveTime::parse_from_str(src, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(src, "%H:%M:%S"))
+        .ok()
+}
+
+fn serialize_time<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error>
+{
+    serializer.serialize_str(&time.format("%H:%M").to_string())
+}
+
+fn deserialize_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error>
+{
+    let src = String::deserialize(deserializer)?;
+
+    parse_alarm_time(&src).ok_or_else(|| serde::de::Error::custom(format!("invalid time `{}`. use \"HH:MM\"", src)))
+}
+
+fn default_true() -> bool
+{
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alarm
+{
+    #[serde(default)]
+    pub id: u32, // 0 = not given yet. see assign_alarm_ids
+    #[serde(serialize_with = "serialize_time", deserialize_with = "deserialize_time")]
+    pub time: NaiveTime,
+    #[serde(default)]
+    pub weekdays: Vec<AlarmDay>,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+impl Alarm
+{
+    pub fn new(time: NaiveTime) -> Self
+    {
+        Self {
+            id: 0,
+            time,
+            weekdays: Vec::new(),
+            label: String::new(),
+            enabled: true,
+        }
+    }
+
+    pub fn is_day(&self, date: &NaiveDate) -> bool
+    {
+        self.weekdays.is_empty() || self.weekdays.contains(&AlarmDay::from_weekday(date.weekday()))
+    }
+
+    // the first time of the alarm after `after` ( exclusive ). None if disabled
+    pub fn next_after(&self, after: &NaiveDateTime) -> Option<NaiveDateTime>
+    {
+        if !self.enabled
+        {
+            return None;
+        }
+
+        (0 ..= 7)
+            .map(|x| (after.date() + TimeDelta::days(x)).and_time(self.time))
+            .find(|x| x > after && self.is_day(&x.date()))
+    }
+
+    // "Mon Tue Fri", "Every day"
+    pub fn weekdays_text(&self) -> String
+    {
+        if self.weekdays.is_empty() || self.weekdays.len() == AlarmDay::ALL.len()
+        {
+            String::from("Every day")
+        }
+        else
+        {
+            AlarmDay::ALL
+                .iter()
+                .filter(|x| self.weekdays.contains(x))
+                .map(|x| x.name())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+}
+
+// the indexes of the alarms of the time in ( from, to ]
+pub fn due_alarms(alarms: &[Alarm], from: &NaiveDateTime, to: &NaiveDateTime) -> Vec<usize>
+{
+    alarms
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| x.next_after(from).is_some_and(|t| t <= *to))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+// the earliest alarm after `now`
+pub fn next_alarm(alarms: &[Alarm], now: &NaiveDateTime) -> Option<(usize, NaiveDateTime)>
+{
+    alarms
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| x.next_after(now).map(|t| (i, t)))
+        .min_by_key(|(_, t)| *t)
+}
+
+// gives the ids from `id_next` to the alarms without an id or with the id of an earlier alarm
+// returns the next id. ids are not reused while the program runs ( a notification may keep an old id )
+pub fn assign_alarm_ids(alarms: &mut [Alarm], id_next: u32) -> u32
+{
+    let mut id_next = alarms.iter().map(|x| x.id + 1).max().unwrap_or(0).max(id_next).max(1);
+
+    let mut used = Vec::<u32>::new();
+
+    for alarm in alarms.iter_mut()
+    {
+        if alarm.id == 0 || used.contains(&alarm.id)
+        {
+            alarm.id = id_next;
+            id_next += 1;
+        }
+
+        used.push(alarm.id);
+    }
+
+    id_next
+}
+
+pub fn alarm_by_id(alarms: &[Alarm], id: u32) -> Option<&Alarm>
+{
+    alarms.iter().find(|x| x.id == id)
+}
+
+// the ids of the alarms to fire in ( from, to ]. the due alarms and the snoozes of `to` or before
+// the fired snoozes and the snoozes of the removed alarms are dropped
+pub fn fire_alarms(alarms: &[Alarm], snoozes: &mut Vec<(u32, NaiveDateTime)>, from: &NaiveDateTime, to: &NaiveDateTime) -> Vec<u32>
+{
+    let mut ret: Vec<u32> = due_alarms(alarms, from, to).iter().map(|x| alarms[*x].id).collect();
+
+    snoozes.retain(|(id, at)| {
+        if alarm_by_id(alarms, *id).is_none()
+        {
+            false
+        }
+        else if *at <= *to
+        {
+            ret.push(*id);
+            false
+        }
+        else
+        {
+            true
+        }
+    });
+
+    ret.sort();
+    ret.dedup();
+
+    ret
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+/*
+    Low precision astronomy for the complications. No network, no ephemeris files.
+
+    see Jean Meeus, Astronomical Algorithms, 2nd ed.
+        ch. 48  Illuminated Fraction of the Moon's Disk
+    see https://en.wikipedia.org/wiki/Sunrise_equation
+*/
+
+const SYNODIC_MONTH: f64 = 29.530588853; // days
+
+fn sin_deg(x: f64) -> f64
+{
+    x.to_radians().sin()
+}
+
+// Julian Day. dt_utc: UTC
+pub fn julian_day(dt_utc: &NaiveDateTime) -> f64
+{
+    // 1970-01-01T00:00:00Z = JD 2440587.5
+
+    dt_utc.and_utc().timestamp_millis() as f64 / (24.0 * 60.0 * 60.0 * 1000.0) + 2440587.5
+}
+
+fn from_julian_day(jd: f64) -> NaiveDateTime
+{
+    let ms = ((jd - 2440587.5) * 24.0 * 60.0 * 60.0 * 1000.0).round() as i64;
+
+    DateTime::from_timestamp_millis(ms).unwrap_or_default().naive_utc()
+}
+
+// Julian centuries from J2000.0
+fn julian_century(dt_utc: &NaiveDateTime) -> f64
+{
+    (julian_day(dt_utc) - 2451545.0) / 36525.0
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MoonPhase
+{
+    pub phase:        f64, // 0.0 = new moon, 0.25 = first quarter, 0.5 = full moon, 0.75 = last quarter
+    pub age:          f64, // days since the new moon
+    pub illumination: f64, // 0.0 .. 1.0 illuminated fraction of the disk
+}
+
+impl MoonPhase
+{
+    pub fn name(&self) -> &'static str
+    {
+        const NAMES: [&str; 8] = [
+            "New Moon",
+            "Waxing Crescent",
+            "First Quarter",
+            "Waxing Gibbous",
+            "Full Moon",
+            "Waning Gibbous",
+            "Last Quarter",
+            "Waning Crescent",
+        ];
+
+        NAMES[((self.phase * 8.0 + 0.5).floor() as usize) % 8]
+    }
+}
+
+pub fn moon_phase(dt_utc: &NaiveDateTime) -> MoonPhase
+{
+    let t = julian_century(dt_utc);
+
+    // mean elongation of the moon, mean anomaly of the sun, mean anomaly of the moon
+
+    let d = (297.8501921 + 445267.1114034 * t).rem_euclid(360.0);
+    let m = (357.5291092 + 35999.0502909 * t).rem_euclid(360.0);
+    let mm = (134.9633964 + 477198.8675055 * t).rem_euclid(360.0);
+
+    // phase angle ( 48.4 )
+
+    let i = 180.0 - d - 6.289 * sin_deg(mm) + 2.100 * sin_deg(m)
+        - 1.274 * sin_deg(2.0 * d - mm)
+        - 0.658 * sin_deg(2.0 * d)
+        - 0.214 * sin_deg(2.0 * mm)
+        - 0.110 * sin_deg(d);
+
+    // elongation 0 .. 360. 180 = full moon
+
+    let elongation = (180.0 - i).rem_euclid(360.0);
+
+    let phase = elongation / 360.0;
+
+    MoonPhase {
+        phase,
+        age: phase * SYNODIC_MONTH,
+        illumination: (1.0 + i.to_radians().cos()) / 2.0,
+    }
+}
+
+// the altitude of the center of the sun in degrees
+pub const SUN_ALTITUDE_SUNRISE: f64 = -0.833; // refraction and the radius of the sun
+pub const SUN_ALTITUDE_CIVIL: f64 = -6.0;
+pub const SUN_ALTITUDE_NAUTICAL: f64 = -12.0;
+pub const SUN_ALTITUDE_ASTRONOMICAL: f64 = -18.0;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SunRiseSet
+{
+    Normal(NaiveDateTime, NaiveDateTime), // ( rise, set ) UTC
+    AlwaysAbove,                          // midnight sun
+    AlwaysBelow,                          // polar night
+}
+
+// ( the julian day of the transit, sin and cos of the declination of the sun )
+fn sun_transit(date: &NaiveDate, longitude: f64) -> (f64, f64, f64)
+{
+    // days from 2000-01-01 12:00 UTC to the noon of the date ( + the difference of TT )
+    let n = (*date - NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()).num_days() as f64 + 0.0008;
+
+    let j_star = n - longitude / 360.0;
+
+    let m = (357.5291 + 0.98560028 * j_star).rem_euclid(360.0);
+    let c = 1.9148 * sin_deg(m) + 0.0200 * sin_deg(2.0 * m) + 0.0003 * sin_deg(3.0 * m);
+    let lambda = (m + c + 180.0 + 102.9372).rem_euclid(360.0);
+
+    let j_transit = 2451545.0 + j_star + 0.0053 * sin_deg(m) - 0.0069 * sin_deg(2.0 * lambda);
+
+    let sin_d = sin_deg(lambda) * sin_deg(23.4397);
+    let cos_d = (1.0 - sin_d * sin_d).sqrt();
+
+    (j_transit, sin_d, cos_d)
+}
+
+// date: the local date. longitude: east is positive. returns UTC
+pub fn solar_noon(date: &NaiveDate, longitude: f64) -> NaiveDateTime
+{
+    from_julian_day(sun_transit(date, longitude).0)
+}
+
+// latitude: north is positive. altitude: SUN_ALTITUDE_xxx
+pub fn sun_rise_set(date: &NaiveDate, latitude: f64, longitude: f64, altitude: f64) -> SunRiseSet
+{
+    let (j_transit, sin_d, cos_d) = sun_transit(date, longitude);
+
+    let cos_w = (sin_deg(altitude) - sin_deg(latitude) * sin_d) / (latitude.to_radians().cos() * cos_d);
+
+    if cos_w < -1.0
+    {
+        SunRiseSet::AlwaysAbove
+    }
+    else if cos_w > 1.0 || cos_w.is_nan()
+    {
+        SunRiseSet::AlwaysBelow
+    }
+    else
+    {
+        let w = cos_w.acos().to_degrees() / 360.0;
+
+        SunRiseSet::Normal(from_julian_day(j_transit - w), from_julian_day(j_transit + w))
+    }
+}
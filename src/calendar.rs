@@ -0,0 +1,118 @@
+use chrono::{FixedOffset, NaiveDateTime, TimeDelta, TimeZone};
+use chrono_tz::OffsetName;
+
+/*
+    Calendar variables of base_text. with_text_calendar = true in the config layer.
+
+    {{year}}            "2025"
+    {{month}}           "10"            01 .. 12
+    {{day}}             "05"            01 .. 31
+    {{weekday}}         "7"             1 ( Monday ) .. 7 ( Sunday ). ISO 8601
+    {{month_name}}      "October"
+    {{month_abbr}}      "Oct"
+    {{weekday_name}}    "Sunday"
+    {{weekday_abbr}}    "Sun"
+    {{day_of_year}}     "278"           001 .. 366
+    {{iso_week}}        "40"            01 .. 53
+    {{iso_year}}        "2025"          the year of the ISO week
+    {{utc_offset}}      "+09:00"
+    {{tz_abbr}}         "JST"           the UTC offset when unknown ( the local time of the system )
+    {{ampm}}            "AM", "PM"
+    {{hour12}}          "07"            01 .. 12
+    {{hour24}}          "19"            00 .. 23
+*/
+
+pub const CALENDAR_VARS: [&str; 16] = [
+    "year",
+    "month",
+    "day",
+    "weekday",
+    "month_name",
+    "month_abbr",
+    "weekday_name",
+    "weekday_abbr",
+    "day_of_year",
+    "iso_week",
+    "iso_year",
+    "utc_offset",
+    "tz_abbr",
+    "ampm",
+    "hour12",
+    "hour24",
+];
+
+pub struct Calendar
+{
+    pub time:    NaiveDateTime, // the local time of the clock
+    pub offset:  TimeDelta,     // time - offset = UTC
+    pub tz_abbr: String,
+}
+
+impl Calendar
+{
+    pub fn new(time: &NaiveDateTime, offset: TimeDelta, time_zone: &str) -> Self
+    {
+        Self {
+            time:    *time,
+            offset,
+            tz_abbr: time_zone_abbr(time_zone, &(*time - offset), offset),
+        }
+    }
+
+    // None for an unknown name
+    pub fn var(&self, name: &str) -> Option<String>
+    {
+        let format = match name
+        {
+            "year" => "%Y",
+            "month" => "%m",
+            "day" => "%d",
+            "weekday" => "%u",
+            "month_name" => "%B",
+            "month_abbr" => "%b",
+            "weekday_name" => "%A",
+            "weekday_abbr" => "%a",
+            "day_of_year" => "%j",
+            "iso_week" => "%V",
+            "iso_year" => "%G",
+            "ampm" => "%p",
+            "hour12" => "%I",
+            "hour24" => "%H",
+            "utc_offset" => return Some(format_utc_offset(self.offset)),
+            "tz_abbr" => return Some(self.tz_abbr.clone()),
+            _ => return None,
+        };
+
+        Some(self.time.format(format).to_string())
+    }
+}
+
+// "+09:00", "-05:30"
+pub fn format_utc_offset(offset: TimeDelta) -> String
+{
+    match FixedOffset::east_opt(offset.num_seconds() as i32)
+    {
+        Some(x) => x.to_string(),
+        None => String::new(),
+    }
+}
+
+// the abbreviation of the time zone at `utc`. "JST", "BST", "GMT+9", ...
+// the time zone is the one of the menu. "" = the local time of the system
+pub fn time_zone_abbr(time_zone: &str, utc: &NaiveDateTime, offset: TimeDelta) -> String
+{
+    if time_zone.starts_with("GMT") || time_zone == "UTC"
+    {
+        return String::from(time_zone);
+    }
+
+    match time_zone.parse::<chrono_tz::Tz>()
+    {
+        Ok(tz) => match tz.offset_from_utc_datetime(utc).abbreviation()
+        {
+            Some(x) => String::from(x),
+            None => format_utc_offset(offset),
+        },
+        _ => format_utc_offset(offset),
+    }
+}
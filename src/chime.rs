@@ -0,0 +1,211 @@
+use chrono::{NaiveDateTime, NaiveTime, TimeDelta, Timelike};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/*
+    Chimes at the hour, the half hour and the quarter hours. Saved in the configuration file.
+
+    [chime]
+    hour = true
+    half_hour = false
+    quarter_hour = false
+    quiet_start = "22:00"           # no chime from quiet_start to quiet_end. may cross midnight
+    quiet_end = "07:00"
+    command = "paplay /usr/share/sounds/freedesktop/stereo/bell.oga"
+    notify = false
+
+    The command gets the time of the chime in the arguments ( `{hour}`, ... in the command are replaced )
+    and in the environment variables.
+
+    placeholder     environment variable        value
+    --------------  --------------------------  -----------------------------------
+    {kind}          SVGCLOCK_CHIME_KIND         hour, half_hour, quarter_hour
+    {hour}          SVGCLOCK_CHIME_HOUR         0 .. 23
+    {hour12}        SVGCLOCK_CHIME_HOUR12       1 .. 12 ( the number of the strikes )
+    {minute}        SVGCLOCK_CHIME_MINUTE       0, 15, 30, 45
+*/
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChimeKind
+{
+    Hour,
+    HalfHour,
+    QuarterHour, // :15 and :45
+}
+
+impl ChimeKind
+{
+    pub fn as_str(&self) -> &'static str
+    {
+        match self
+        {
+            ChimeKind::Hour => "hour",
+            ChimeKind::HalfHour => "half_hour",
+            ChimeKind::QuarterHour => "quarter_hour",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Chime
+{
+    pub kind: ChimeKind,
+    pub time: NaiveDateTime,
+}
+
+impl Chime
+{
+    // the time at the boundary of the quarter hours
+    pub fn at(time: &NaiveDateTime) -> Option<Self>
+    {
+        if time.second() != 0 || time.nanosecond() != 0
+        {
+            return None;
+        }
+
+        let kind = match time.minute()
+        {
+            0 => ChimeKind::Hour,
+            30 => ChimeKind::HalfHour,
+            15 | 45 => ChimeKind::QuarterHour,
+            _ => return None,
+        };
+
+        Some(Self { kind, time: *time })
+    }
+
+    pub fn hour12(&self) -> u32
+    {
+        self.time.hour12().1
+    }
+
+    // ( name of environment variable, placeholder, value )
+    pub fn vars(&self) -> Vec<(&'static str, &'static str, String)>
+    {
+        vec![
+            ("SVGCLOCK_CHIME_KIND", "{kind}", String::from(self.kind.as_str())),
+            ("SVGCLOCK_CHIME_HOUR", "{hour}", self.time.hour().to_string()),
+            ("SVGCLOCK_CHIME_HOUR12", "{hour12}", self.hour12().to_string()),
+            ("SVGCLOCK_CHIME_MINUTE", "{minute}", self.time.minute().to_string()),
+        ]
+    }
+
+    pub fn expand_command(&self, command: &str) -> String
+    {
+        self.vars()
+            .iter()
+            .fold(String::from(command), |ret, (_, placeholder, value)| ret.replace(placeholder, value))
+    }
+}
+
+// the last boundary of the quarter hours in ( from, to ]
+pub fn chime_between(from: &NaiveDateTime, to: &NaiveDateTime) -> Option<Chime>
+{
+    if to <= from
+    {
+        return None;
+    }
+
+    let quarter = to
+        .date()
+        .and_hms_opt(to.hour(), to.minute() / 15 * 15, 0)?;
+
+    if quarter > *from
+    {
+        Chime::at(&quarter)
+    }
+    else
+    {
+        None
+    }
+}
+
+fn serialize_time_opt<S: Serializer>(time: &Option<NaiveTime>, serializer: S) -> Result<S::Ok, S::Error>
+{
+    match time
+    {
+        Some(x) => serializer.serialize_some(&x.format("%H:%M").to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+// "HH:MM". the quiet hours in the configuration file and in the dialog
+pub fn parse_quiet_time(src: &str) -> Option<NaiveTime>
+{
+    NaiveTime::parse_from_str(src.trim(), "%H:%M").ok()
+}
+
+fn deserialize_time_opt<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NaiveTime>, D::Error>
+{
+    match Option::<String>::deserialize(deserializer)?
+    {
+        Some(src) => parse_quiet_time(&src)
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid time `{}`. use \"HH:MM\"", src))),
+        None => Ok(None),
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChimeConfig
+{
+    pub hour: bool,
+    pub half_hour: bool,
+    pub quarter_hour: bool,
+    #[serde(serialize_with = "serialize_time_opt", deserialize_with = "deserialize_time_opt")]
+    pub quiet_start: Option<NaiveTime>,
+    #[serde(serialize_with = "serialize_time_opt", deserialize_with = "deserialize_time_opt")]
+    pub quiet_end: Option<NaiveTime>,
+    pub command: Option<String>,
+    pub notify: bool,
+}
+
+impl ChimeConfig
+{
+    pub const fn new() -> Self
+    {
+        Self {
+            hour: false,
+            half_hour: false,
+            quarter_hour: false,
+            quiet_start: None,
+            quiet_end: None,
+            command: None,
+            notify: false,
+        }
+    }
+
+    pub fn is_enabled(&self, kind: ChimeKind) -> bool
+    {
+        match kind
+        {
+            ChimeKind::Hour => self.hour,
+            ChimeKind::HalfHour => self.half_hour,
+            ChimeKind::QuarterHour => self.quarter_hour,
+        }
+    }
+
+    // quiet_start <= time < quiet_end
+    pub fn is_quiet(&self, time: &NaiveTime) -> bool
+    {
+        match (self.quiet_start, self.quiet_end)
+        {
+            (Some(start), Some(end)) if start <= end => start <= *time && *time < end,
+            (Some(start), Some(end)) => start <= *time || *time < end,
+            _ => false,
+        }
+    }
+
+    // the chime to ring when the time of the clock moves from `from` to `to`
+    // a jump longer than `max_gap` ( suspend, a change of the time zone ) rings nothing
+    pub fn check(&self, from: &NaiveDateTime, to: &NaiveDateTime, max_gap: TimeDelta) -> Option<Chime>
+    {
+        if *to - *from > max_gap
+        {
+            return None;
+        }
+
+        chime_between(from, to).filter(|x| self.is_enabled(x.kind) && !self.is_quiet(&x.time.time()))
+    }
+}
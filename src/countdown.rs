@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/*
+    A countdown timer.
+
+    Saved in the configuration file, so the end is the time of the wall clock ( UTC ), not a monotonic clock.
+    A timer that ended while the program was not running expires at the next start.
+*/
+
+pub const COUNTDOWN_MAX: Duration = Duration::from_secs(100 * 3600);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Countdown
+{
+    end:      Option<i64>, // unix time in milliseconds. None = not running
+    duration: u64,         // milliseconds. the last duration started
+}
+
+impl Countdown
+{
+    pub const fn new() -> Self
+    {
+        Self {
+            end:      None,
+            duration: 0,
+        }
+    }
+
+    // the duration is limited to COUNTDOWN_MAX
+    pub fn start(&mut self, now: &DateTime<Utc>, duration: Duration)
+    {
+        self.duration = duration.min(COUNTDOWN_MAX).as_millis() as u64;
+        self.end = Some(now.timestamp_millis().saturating_add(self.duration as i64));
+    }
+
+    pub fn cancel(&mut self)
+    {
+        self.end = None;
+    }
+
+    pub fn is_running(&self) -> bool
+    {
+        self.end.is_some()
+    }
+
+    pub fn duration(&self) -> Duration
+    {
+        Duration::from_millis(self.duration)
+    }
+
+    // None = not running. ZERO = expired
+    pub fn remaining(&self, now: &DateTime<Utc>) -> Option<Duration>
+    {
+        self.end
+            .map(|x| Duration::from_millis((x - now.timestamp_millis()).max(0) as u64))
+    }
+
+    // the remaining fraction of the duration. 1.0 at the start .. 0.0 at the end
+    pub fn fraction(&self, now: &DateTime<Utc>) -> f64
+    {
+        match self.remaining(now)
+        {
+            Some(x) if self.duration > 0 => (x.as_millis() as f64 / self.duration as f64).clamp(0.0, 1.0),
+            _ => 0.0,
+        }
+    }
+
+    // true once at the end. the timer stops
+    pub fn check_expired(&mut self, now: &DateTime<Utc>) -> bool
+    {
+        if self.remaining(now).is_some_and(|x| x.is_zero())
+        {
+            self.end = None;
+            true
+        }
+        else
+        {
+            false
+        }
+    }
+}
+
+// "04:59", "1:30:00". rounded up to the second
+pub fn format_remaining(remaining: Duration) -> String
+{
+    let secs = remaining.as_millis().div_ceil(1000) as u64;
+
+    if secs >= 3600
+    {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    }
+    else
+    {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/*
+    "25"        minutes
+    "4:30"      minutes:seconds
+    "1:30:00"   hours:minutes:seconds
+    "1h30m", "90s", "2m30s"
+
+    None for 0 and over COUNTDOWN_MAX
+*/
+pub fn parse_duration(src: &str) -> Option<Duration>
+{
+    let src = src.trim().to_lowercase();
+
+    let secs = if src.contains(':')
+    {
+        let parts: Vec<u64> = src.split(':').map(|x| x.trim().parse::<u64>().ok()).collect::<Option<_>>()?;
+
+        match parts.as_slice()
+        {
+            [m, s] if *s < 60 => m.checked_mul(60)?.checked_add(*s)?,
+            [h, m, s] if *m < 60 && *s < 60 => h.checked_mul(3600)?.checked_add(m * 60 + s)?,
+            _ => return None,
+        }
+    }
+    else if let Ok(x) = src.parse::<u64>()
+    {
+        x.checked_mul(60)?
+    }
+    else
+    {
+        let mut ret: u64 = 0;
+        let mut num = String::new();
+
+        for c in src.chars()
+        {
+            match c
+            {
+                '0' ..= '9' => num.push(c),
+                'h' | 'm' | 's' =>
+                {
+                    let x = num.parse::<u64>().ok()?;
+
+                    let secs = match c
+                    {
+                        'h' => x.checked_mul(3600)?,
+                        'm' => x.checked_mul(60)?,
+                        _ => x,
+                    };
+
+                    ret = ret.checked_add(secs)?;
+
+                    num.clear();
+                },
+                ' ' =>
+                {},
+                _ => return None,
+            }
+        }
+
+        if !num.is_empty()
+        {
+            return None;
+        }
+
+        ret
+    };
+
+    if secs > 0 && secs <= COUNTDOWN_MAX.as_secs()
+    {
+        Some(Duration::from_secs(secs))
+    }
+    else
+    {
+        None
+    }
+}
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+/*
+    The mapping from the value of a hand ( see TimeDriver ) to the angle of the hand.
+
+    start_angle     the angle at value_min. degrees, 0 = 12 o'clock, clockwise. default = 0
+    end_angle       the angle at value_max. default = start_angle + 360
+    value_min       default = 0
+    value_max       default = the range of the driver
+    curve           linear, quad_in, quad_out, ... default = linear
+    points          [[value, angle], ...] a non-linear scale. start_angle .. curve are ignored
+    snap_back       seconds. the duration of the move from end to start. default = 1.0
+
+    A hand that does not make a full turn ( retrograde hand ) moves back from the angle of
+    value_max to the angle of value_min when the driver starts a new turn ( value = 0 ).
+*/
+
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandCurve
+{
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+}
+
+impl HandCurve
+{
+    // 0.0 <= t <= 1.0 -> 0.0 .. 1.0
+    pub fn apply(&self, t: f64) -> f64
+    {
+        let p = t as f32;
+
+        match self
+        {
+            HandCurve::Linear => t,
+            HandCurve::QuadIn => tween::QuadIn.tween(1.0, p),
+            HandCurve::QuadOut => tween::QuadOut.tween(1.0, p),
+            HandCurve::QuadInOut => tween::QuadInOut.tween(1.0, p),
+            HandCurve::CubicIn => tween::CubicIn.tween(1.0, p),
+            HandCurve::CubicOut => tween::CubicOut.tween(1.0, p),
+            HandCurve::CubicInOut => tween::CubicInOut.tween(1.0, p),
+            HandCurve::SineIn => tween::SineIn.tween(1.0, p),
+            HandCurve::SineOut => tween::SineOut.tween(1.0, p),
+            HandCurve::SineInOut => tween::SineInOut.tween(1.0, p),
+        }
+    }
+}
+
+const SNAP_BACK_SECS_DEFAULT: f64 = 1.0;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HandRange
+{
+    pub start_angle: Option<f64>,
+    pub end_angle: Option<f64>,
+    pub value_min: Option<f64>,
+    pub value_max: Option<f64>,
+    pub curve: Option<HandCurve>,
+    pub points: Option<Vec<(f64, f64)>>,
+    pub snap_back: Option<f64>,
+}
+
+impl HandRange
+{
+    fn get_points(&self) -> Option<&Vec<(f64, f64)>>
+    {
+        self.points.as_ref().filter(|x| !x.is_empty())
+    }
+
+    fn min_value(&self) -> f64
+    {
+        match self.get_points()
+        {
+            Some(x) => x[0].0,
+            None => self.value_min.unwrap_or(0.0),
+        }
+    }
+
+    fn max_value(&self, range: f64) -> f64
+    {
+        match self.get_points()
+        {
+            Some(x) => x[x.len() - 1].0,
+            None => self.value_max.unwrap_or(range),
+        }
+    }
+
+    pub fn is_full_turn(&self) -> bool
+    {
+        if self.get_points().is_some()
+        {
+            false
+        }
+        else
+        {
+            let start = self.start_angle.unwrap_or(0.0);
+            let end = self.end_angle.unwrap_or(start + 360.0);
+
+            ((end - start).abs() - 360.0).abs() < 1e-9
+        }
+    }
+
+    // range: the range of the driver
+    pub fn angle(&self, value: f64, range: f64) -> f64
+    {
+        if let Some(points) = self.get_points()
+        {
+            // piecewise linear. clamped at both ends
+
+            if value <= points[0].0
+            {
+                return points[0].1;
+            }
+
+            for w in points.windows(2)
+            {
+                let ((v0, a0), (v1, a1)) = (w[0], w[1]);
+
+                if value < v1
+                {
+                    return if v1 > v0
+                    {
+                        a0 + (a1 - a0) * (value - v0) / (v1 - v0)
+                    }
+                    else
+                    {
+                        a1
+                    };
+                }
+            }
+
+            points[points.len() - 1].1
+        }
+        else
+        {
+            let start = self.start_angle.unwrap_or(0.0);
+            let end = self.end_angle.unwrap_or(start + 360.0);
+
+            let min = self.min_value();
+            let max = self.max_value(range);
+
+            let t = if max > min
+            {
+                ((value - min) / (max - min)).clamp(0.0, 1.0)
+            }
+            else
+            {
+                0.0
+            };
+
+            start + (end - start) * self.curve.unwrap_or_default().apply(t)
+        }
+    }
+
+    // elapsed: seconds since the driver started a new turn
+    pub fn angle_snap_back(&self, value: f64, range: f64, elapsed: f64) -> f64
+    {
+        let angle = self.angle(value, range);
+
+        let snap_back = self.snap_back.unwrap_or(SNAP_BACK_SECS_DEFAULT);
+
+        if !self.is_full_turn() && snap_back > 0.0 && elapsed >= 0.0 && elapsed < snap_back
+        {
+            let angle_max = self.angle(self.max_value(range), range);
+
+            angle_max + tween::QuadInOut.tween(angle - angle_max, (elapsed / snap_back) as f32)
+        }
+        else
+        {
+            angle
+        }
+    }
+}
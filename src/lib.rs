@@ -1 +1,13 @@
-pub mod minitemplate;
\ No newline at end of file
+pub mod alarm;
+pub mod astro;
+pub mod calendar;
+pub mod chime;
+pub mod countdown;
+pub mod handrange;
+pub mod minitemplate;
+pub mod segment;
+pub mod stopwatch;
+pub mod svglayer;
+pub mod svgtransform;
+pub mod timedriver;
+pub mod wordclock;
\ No newline at end of file
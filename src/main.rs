@@ -13,10 +13,10 @@ use std::str::FromStr;
 use std::{io::Cursor, str};
 
 use std::cell::RefCell;
-use std::collections::HashMap;
 use std::f64::consts::PI;
 use std::rc::Rc;
 use std::sync::LazyLock;
+use std::time::Instant;
 
 // use log::{Level, log_enabled};
 
@@ -24,14 +24,15 @@ use strum::IntoEnumIterator;
 
 use serde::{Deserialize, Serialize};
 
-use glam::{DAffine2, DMat2, DVec2, IVec2};
-use quick_xml::events::{BytesRef, BytesStart};
+use glam::{DVec2, IVec2};
+use quick_xml::events::BytesRef;
 use regex::Regex;
 
 use gtk::prelude::*;
-use gtk::{AboutDialog /* Dialog, DialogFlags, ResponseType */};
+use gtk::{AboutDialog, Button, CheckButton, Dialog, DialogFlags, Entry, Grid, Label};
 use gtk::{Application, ApplicationWindow, DrawingArea};
 use gtk::{CheckMenuItem, Menu, MenuItem, SeparatorMenuItem};
+use gtk::{FileChooserAction, FileChooserDialog, FileFilter, ResponseType};
 
 use gtk::cairo::{Context, Format, ImageSurface, Rectangle, Region};
 // use gtk::cairo::{ FontSlant, FontWeight  };
@@ -45,12 +46,26 @@ use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, TimeDelta, Timelike, Utc
 
 use linked_hash_map::LinkedHashMap;
 
+use svgclock_rs::alarm::{Alarm, AlarmDay, alarm_by_id, assign_alarm_ids, fire_alarms, next_alarm, parse_alarm_time};
+use svgclock_rs::astro::*;
+use svgclock_rs::calendar::{CALENDAR_VARS, Calendar};
+use svgclock_rs::chime::{Chime, ChimeConfig, parse_quiet_time};
+use svgclock_rs::countdown::{Countdown, format_remaining, parse_duration};
+use svgclock_rs::handrange::HandRange;
 use svgclock_rs::minitemplate::*;
+use svgclock_rs::segment::{DIGIT_POSITIONS, digit, dot_matrix_is_on, seven_segment_is_on, sixteen_segment_is_on};
+use svgclock_rs::stopwatch::{ChronoDriver, Stopwatch, format_elapsed};
+use svgclock_rs::svglayer::{self, get_layer_ns_keys, is_layer_name};
+use svgclock_rs::svgtransform::{find_center, parse_float_list};
+use svgclock_rs::timedriver::TimeDriver;
+use svgclock_rs::wordclock::{WordClockLang, word_clock_dots, word_clock_words};
 
 
 const ENV_KEY_THEME_CUSTOM: &str = "THEME_CUSTOM";
 const ENV_KEY_FIX_TIME: &str = "FIX_TIME";
 
+const ARG_CHECK_THEME: &str = "--check-theme";
+
 const ENABLE_FILE_INCLUDE: bool = true;
 
 const INCLUDE_BYTES_LOGO_PNG: &'static [u8] = include_bytes!("../logo128.png");
@@ -73,8 +88,40 @@ const FILE_CLOCL_THEME_6_SVG: &str = "clock_theme_6.svg";
 const FILE_CLOCL_THEME_7_SVG: &str = "clock_theme_7.svg";
 const FILE_CLOCL_THEME_8_SVG: &str = "clock_theme_8.svg";
 
+const BUILTIN_THEMES: [(&'static [u8], &str); 8] = [
+    (INCLUDE_BYTES_CLOCL_THEME_1_SVG, FILE_CLOCL_THEME_1_SVG),
+    (INCLUDE_BYTES_CLOCL_THEME_2_SVG, FILE_CLOCL_THEME_2_SVG),
+    (INCLUDE_BYTES_CLOCL_THEME_3_SVG, FILE_CLOCL_THEME_3_SVG),
+    (INCLUDE_BYTES_CLOCL_THEME_4_SVG, FILE_CLOCL_THEME_4_SVG),
+    (INCLUDE_BYTES_CLOCL_THEME_5_SVG, FILE_CLOCL_THEME_5_SVG),
+    (INCLUDE_BYTES_CLOCL_THEME_6_SVG, FILE_CLOCL_THEME_6_SVG),
+    (INCLUDE_BYTES_CLOCL_THEME_7_SVG, FILE_CLOCL_THEME_7_SVG),
+    (INCLUDE_BYTES_CLOCL_THEME_8_SVG, FILE_CLOCL_THEME_8_SVG),
+];
+
+const DIR_THEME_SYSTEM: &str = "svgclock-rs/theme"; // under $XDG_DATA_DIRS ( /usr/share, ... )
+const DIR_THEME_USER: &str = "svgclock-rs/themes"; // under $XDG_DATA_HOME
+const DIR_THEME_EXE: &str = "theme"; // beside the executable ( windows zip )
+
+const THEME_ID_DEFAULT: &str = "clock_theme_1";
+const THEME_ID_CUSTOM_PREFIX: &str = "custom:";
+const THEME_ERROR_IMAGE_SIZE: i32 = 256; // the clock without any theme to load
+
 const UPDATE_CYCLE_SLOW: u64 = 100;
 const UPDATE_CYCLE_FAST: u64 = 25;
+const UPDATE_CYCLE_EVENTS: u64 = 1000; // alarms, countdown, chimes. not by the drawing ( a minimized window is not drawn )
+
+const DIGIT_TRANSITION_MILLIS: i64 = 300;
+
+const COUNTDOWN_PRESETS: [u64; 8] = [1, 3, 5, 10, 15, 25, 30, 60]; // minutes
+const COUNTDOWN_FLASH_SECS: f64 = 10.0;
+
+const ALARM_SNOOZE_MINUTES: i64 = 5;
+const ALARM_CATCH_UP_MINUTES: i64 = 10; // the alarms missed by a longer gap ( suspend, time zone change ) are skipped
+const ACTION_ALARM_SNOOZE: &str = "alarm-snooze";
+const ACTION_ALARM_DISMISS: &str = "alarm-dismiss";
+
+const CHIME_MAX_GAP_SECS: i64 = 60; // no chime after a longer jump of the time
 
 const FILE_APP_INFO: &str = ".svgclock-rs";
 const FILE_SNAPSHOTO_PNG: &str = "snapshot.png";
@@ -152,103 +199,6 @@ fn to_char(br: &BytesRef) -> Option<char>
     None
 }
 
-fn parse_float_list(val: &str) -> Vec<f64>
-{
-    const RE_FLOAT: LazyLock<Regex> = LazyLock::new(|| {
-        Regex::new(r"[-+]?([0-9]*\.[0-9]+|[0-9]+\.?[0-9]*)([eE][-+]?[0-9]+)?").unwrap()
-    });
-
-    let arg: Vec<f64> = RE_FLOAT
-        .captures_iter(val)
-        .map(|x| f64::from_str(&x[0]))
-        .filter(|x| if let Ok(_) = x { true } else { false })
-        .map(|x| x.unwrap_or_default())
-        .collect();
-
-    arg
-}
-
-fn parse_svg_transform_value(transform: &str) -> Option<DAffine2>
-{
-    const RE_TRANSLATE: LazyLock<Regex> = LazyLock::new(|| {
-        Regex::new(r"(?i)(translate|scale|rotate|skewX|skewY|matrix)\s*\(([^\)]+)\)").unwrap()
-    });
-
-    if String::from(transform).trim() == ""
-    {
-        None
-    }
-    else
-    {
-        let mut ret = DAffine2::IDENTITY;
-
-        for caps in RE_TRANSLATE.captures_iter(transform)
-        {
-            let op = caps[1].to_lowercase();
-
-            let arg: Vec<f64> = parse_float_list(&caps[2]);
-
-            let m = match op.as_str()
-            {
-                "translate" if arg.len() == 2 =>
-                {
-                    Some(DAffine2::from_translation(DVec2 {
-                        x: arg[0],
-                        y: arg[1],
-                    }))
-                },
-                "scale" if arg.len() == 2 =>
-                {
-                    Some(DAffine2::from_scale(DVec2 {
-                        x: arg[0],
-                        y: arg[1],
-                    }))
-                },
-                "rotate" if arg.len() == 1 => Some(DAffine2::from_angle(arg[0])),
-                "rotate" if arg.len() == 3 =>
-                {
-                    Some(DAffine2::from_angle_translation(
-                        arg[0],
-                        DVec2 {
-                            x: arg[1],
-                            y: arg[2],
-                        },
-                    ))
-                },
-                "skewx" if arg.len() == 2 => None,
-                "skewy" if arg.len() == 2 => None,
-                "matrix" if arg.len() == 3 =>
-                {
-                    Some(DAffine2::from_mat2_translation(
-                        DMat2 {
-                            x_axis: DVec2 {
-                                x: arg[0],
-                                y: arg[1],
-                            },
-                            y_axis: DVec2 {
-                                x: arg[2],
-                                y: arg[3],
-                            },
-                        },
-                        DVec2 {
-                            x: arg[4],
-                            y: arg[5],
-                        },
-                    ))
-                },
-                _ => None,
-            };
-
-            if let Some(m) = m
-            {
-                ret *= m;
-            }
-        }
-
-        Some(ret)
-    }
-}
-
 type XmlInputReader<'a> = quick_xml::Reader<&'a [u8]>;
 
 fn parse_xml_sz_and_vbox(src_buf: &[u8]) -> Result<(IVec2, DVec2, DVec2), Box<dyn Error>>
@@ -332,14 +282,45 @@ fn parse_xml_sz_and_vbox(src_buf: &[u8]) -> Result<(IVec2, DVec2, DVec2), Box<dy
 
 fn parse_xml_config(src_buf: &[u8]) -> Result<ImageInfoConfig, Box<dyn Error>>
 {
-    fn func_get_text(r_src: &mut XmlInputReader) -> Result<String, Box<dyn Error>>
+    let text = parse_xml_config_text(src_buf)?;
+
+    // an error is not ignored. the theme without the config would lose its hands, wheels, ...
+    match toml::from_str::<ImageInfoConfig>(&text)
     {
-        const TARGET_TAG_TSPAN: &[u8] = "tspan".as_bytes();
+        Ok(x) => Ok(x),
+        Err(err) => Err(format!("config : {}", err).into()),
+    }
+}
+
+fn parse_xml_config_text(src_buf: &[u8]) -> Result<String, Box<dyn Error>>
+{
+    let (text, _) = parse_xml_config_text_pos(src_buf)?;
+
+    debug!("config text : {:?}", text);
 
-        let mut text = String::new();
+    Ok(text)
+}
+
+// the text of the config layer, with the position in src_buf of each byte of the text ( for check_theme )
+fn parse_xml_config_text_pos(src_buf: &[u8]) -> Result<(String, Vec<usize>), Box<dyn Error>>
+{
+    fn func_push(text: &mut String, text_pos: &mut Vec<usize>, src: &str, pos: impl Fn(usize) -> usize)
+    {
+        text.push_str(src);
+        text_pos.extend((0 .. src.len()).map(pos));
+    }
+
+    fn func_get_text(
+        r_src: &mut XmlInputReader,
+        text: &mut String,
+        text_pos: &mut Vec<usize>,
+    ) -> Result<(), Box<dyn Error>>
+    {
+        const TARGET_TAG_TSPAN: &[u8] = "tspan".as_bytes();
 
         loop
         {
+            let pos = r_src.buffer_position() as usize;
             let event = r_src.read_event();
 
             match event
@@ -349,7 +330,7 @@ fn parse_xml_config(src_buf: &[u8]) -> Result<ImageInfoConfig, Box<dyn Error>>
                 {
                     if tag.name().as_ref() == TARGET_TAG_TSPAN
                     {
-                        text += "\n";
+                        func_push(text, text_pos, "\n", |_| pos);
                         break;
                     }
                 },
@@ -357,32 +338,23 @@ fn parse_xml_config(src_buf: &[u8]) -> Result<ImageInfoConfig, Box<dyn Error>>
                 {
                     if tag.name().as_ref() == TARGET_TAG_TSPAN
                     {
-                        match func_get_text(r_src)
-                        {
-                            Ok(x) =>
-                            {
-                                text += &x;
-                            },
-                            Err(x) =>
-                            {
-                                return Err(x);
-                            },
-                        }
+                        func_get_text(r_src, text, text_pos)?;
                     }
                 },
                 Ok(quick_xml::events::Event::Text(inner)) =>
                 {
-                    text += std::str::from_utf8(inner.as_ref()).unwrap();
+                    func_push(text, text_pos, std::str::from_utf8(inner.as_ref()).unwrap(), |x| pos + x);
                 },
                 Ok(quick_xml::events::Event::CData(inner)) =>
                 {
-                    text += std::str::from_utf8(inner.as_ref()).unwrap();
+                    // after "<![CDATA["
+                    func_push(text, text_pos, std::str::from_utf8(inner.as_ref()).unwrap(), |x| pos + 9 + x);
                 },
                 Ok(quick_xml::events::Event::GeneralRef(inner)) =>
                 {
                     if let Some(x) = to_char(&inner)
                     {
-                        text.push(x);
+                        func_push(text, text_pos, x.encode_utf8(&mut [0; 4]), |_| pos);
                     }
                 },
                 Err(x) =>
@@ -394,14 +366,15 @@ fn parse_xml_config(src_buf: &[u8]) -> Result<ImageInfoConfig, Box<dyn Error>>
             }
         }
 
-        Ok(text)
+        Ok(())
     }
 
     let target_tag = "text".as_bytes();
 
     let mut text = String::new();
+    let mut text_pos = Vec::<usize>::new();
 
-    let mut r_src = XmlInputReader::from_reader(&src_buf);
+    let mut r_src = XmlInputReader::from_reader(src_buf);
 
     loop
     {
@@ -414,17 +387,7 @@ fn parse_xml_config(src_buf: &[u8]) -> Result<ImageInfoConfig, Box<dyn Error>>
             {
                 if tag.name().as_ref() == target_tag
                 {
-                    match func_get_text(&mut r_src)
-                    {
-                        Ok(x) =>
-                        {
-                            text += &x;
-                        },
-                        Err(x) =>
-                        {
-                            return Err(x);
-                        },
-                    }
+                    func_get_text(&mut r_src, &mut text, &mut text_pos)?;
                     break;
                 }
             },
@@ -433,22 +396,10 @@ fn parse_xml_config(src_buf: &[u8]) -> Result<ImageInfoConfig, Box<dyn Error>>
         }
     }
 
-    debug!("config text : {:?}", text);
-
-    let ret: ImageInfoConfig = match toml::from_str(&text)
-    {
-        Ok(x) => x,
-        Err(x) =>
-        {
-            error!("config error. {:?}", x);
-            ImageInfoConfig::new()
-        },
-    };
-
-    Ok(ret)
+    Ok((text, text_pos))
 }
 
-#[derive(Debug, strum::EnumString, strum::Display)]
+#[derive(Debug, Copy, Clone, strum::EnumString, strum::Display, strum::EnumIter)]
 enum LayerTarget
 {
     #[strum(to_string = "base")]
@@ -469,265 +420,231 @@ enum LayerTarget
     SubSecondHandle,
     #[strum(to_string = "sub_second_center_circle")]
     SubSecondCenterCircle,
+    #[strum(to_string = "moon_phase_disc")]
+    MoonPhaseDisc,
+    #[strum(to_string = "moon_phase_center_circle")]
+    MoonPhaseCenterCircle,
+    #[strum(to_string = "moon_phase_aperture")]
+    MoonPhaseAperture,
+    #[strum(to_string = "chrono_second_handle")]
+    ChronoSecondHandle,
+    #[strum(to_string = "chrono_minute_handle")]
+    ChronoMinuteHandle,
+    #[strum(to_string = "chrono_hour_handle")]
+    ChronoHourHandle,
+    #[strum(to_string = "countdown_handle")]
+    CountdownHandle,
+    #[strum(to_string = "alarm_handle")]
+    AlarmHandle,
+    #[strum(to_string = "gmt_handle")]
+    GmtHandle,
     #[strum(to_string = "config")]
     Config,
 }
 
-fn parse_xml_center(src_buf: &[u8], target: LayerTarget) -> Result<DVec2, Box<dyn Error>>
+fn parse_xml_center(src_buf: &[u8], target: LayerTarget) -> Result<Option<DVec2>, Box<dyn Error>>
 {
-    const TARGET_ATTR_KEY_TRANSFORM: &[u8] = "transform".as_bytes();
-
-    const TARGET_TAG: &[u8] = "g".as_bytes();
-    const TARGET_ATTR_KEY_GROUPMODE: &[u8] = "inkscape:groupmode".as_bytes();
-    const TARGET_ATTR_VAL_GROUPMODE: &[u8] = "layer".as_bytes();
-    const TARGET_ATTR_KEY_LABEL: &[u8] = "inkscape:label".as_bytes();
-
-    const TARGET_TAG_ELLIPSE: &[u8] = "ellipse".as_bytes();
-    const TARGET_TAG_CIRCLE: &[u8] = "circle".as_bytes();
-    const TARGET_ATTR_KEY_CX: &[u8] = "cx".as_bytes();
-    const TARGET_ATTR_KEY_CY: &[u8] = "cy".as_bytes();
+    parse_xml_center_layer(src_buf, &target.to_string())
+}
 
-    let mut ret = DVec2::ZERO;
+fn parse_xml_center_layer(src_buf: &[u8], name: &str) -> Result<Option<DVec2>, Box<dyn Error>>
+{
+    let ns_keys = get_layer_ns_keys(src_buf);
 
-    let mut translate_affines: Vec<DAffine2> = Vec::new();
+    Ok(find_center(src_buf, |tag, _| is_layer_name(tag, name, &ns_keys))?)
+}
 
-    let mut target_layer = false;
+fn filter_xml(src_buf: &[u8], target: LayerTarget) -> Result<Option<Vec<u8>>, Box<dyn Error>>
+{
+    filter_xml_layer(src_buf, &target.to_string())
+}
 
-    let get_transform_affine = |tag: &BytesStart<'_>| {
-        if let Ok(attr) = tag.try_get_attribute(TARGET_ATTR_KEY_TRANSFORM)
-            && let Some(attr) = attr
-        {
-            if let Ok(attr_transform) = std::str::from_utf8(attr.value.as_ref())
-            {
-                // debug!("attr_translate:{:?}", attr_transform);
-                if let Some(x) = parse_svg_transform_value(attr_transform)
-                {
-                    return x;
-                }
-            }
-        }
+fn filter_xml_layer(src_buf: &[u8], name: &str) -> Result<Option<Vec<u8>>, Box<dyn Error>>
+{
+    svglayer::filter_xml_layer(src_buf, name, &get_layer_names_known(src_buf))
+}
 
-        DAffine2::IDENTITY
-    };
+fn get_layer_names_known(src_buf: &[u8]) -> Vec<String>
+{
+    // the layers of LayerTarget and the layers declared in the config
 
-    let mut r_src = XmlInputReader::from_reader(&src_buf);
+    let mut ret: Vec<String> = LayerTarget::iter().map(|x| x.to_string()).collect();
 
-    loop
+    if let Ok(Some(src_xml)) = svglayer::filter_xml_layer(src_buf, &LayerTarget::Config.to_string(), &ret)
+        && let Ok(text) = parse_xml_config_text(&src_xml)
+        && let Ok(config) = toml::from_str::<ImageInfoConfig>(&text)
     {
-        let event = r_src.read_event();
-
-        match event
-        {
-            Ok(quick_xml::events::Event::Eof) => break,
-            Ok(evt) =>
-            {
-                match evt
-                {
-                    quick_xml::events::Event::Start(ref tag) =>
-                    {
-                        translate_affines.push(get_transform_affine(tag));
-
-                        // check <g>
-
-                        if translate_affines.len() == 2 && tag.name().as_ref() == TARGET_TAG
-                        {
-                            target_layer = false;
-
-                            if let Ok(attr) = tag.try_get_attribute(TARGET_ATTR_KEY_GROUPMODE)
-                                && let Some(attr) = attr
-                            {
-                                if attr.value.as_ref() == TARGET_ATTR_VAL_GROUPMODE
-                                {
-                                    if let Ok(attr) = tag.try_get_attribute(TARGET_ATTR_KEY_LABEL)
-                                        && let Some(attr) = attr
-                                    {
-                                        if attr.value.as_ref() == target.to_string().as_bytes()
-                                        {
-                                            target_layer = true;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    },
-                    quick_xml::events::Event::Empty(ref tag) =>
-                    {
-                        /*
-                        debug!("target_layer: {:?} ", target_layer);
-                        debug!("depth: {:?} ", translate_affines.len());
-                        debug!("tag: {:?} ", tag);
-                        */
-
-                        if target_layer
-                            && translate_affines.len() == 2
-                            && (tag.name().as_ref() == TARGET_TAG_ELLIPSE
-                                || tag.name().as_ref() == TARGET_TAG_CIRCLE)
-                        {
-                            let mut tran_affine = DAffine2::IDENTITY;
-                            // debug!("tran_affine A: {:?}", tran_affine);
-
-                            for x in &translate_affines
-                            {
-                                tran_affine *= x;
-                                // debug!("tran_affine B: {:?}", tran_affine);
-                            }
-
-                            tran_affine *= get_transform_affine(tag);
-                            //debug!("tran_affine C: {:?}", tran_affine);
-
-                            let mut vec2 = DVec2 {
-                                x: 0.0, y: 0.0
-                            };
-
-                            if let Ok(attr) = tag.try_get_attribute(TARGET_ATTR_KEY_CX)
-                                && let Some(attr) = attr
-                            {
-                                if let Ok(num) =
-                                    f64::from_str(std::str::from_utf8(attr.value.as_ref()).unwrap())
-                                {
-                                    vec2.x = num;
-                                }
-                            }
-
-                            if let Ok(attr) = tag.try_get_attribute(TARGET_ATTR_KEY_CY)
-                                && let Some(attr) = attr
-                            {
-                                if let Ok(num) =
-                                    f64::from_str(std::str::from_utf8(attr.value.as_ref()).unwrap())
-                                {
-                                    vec2.y = num;
-                                }
-                            }
-
-                            ret = tran_affine.transform_point2(vec2);
-                            // debug!("ret: {:?}", ret);
-                        }
-                    },
-                    quick_xml::events::Event::End(ref _tag) =>
-                    {
-                        translate_affines.pop();
-                    },
-                    _ =>
-                    {},
-                }
-            },
-            Err(e) => return Err(Box::new(e)),
-        }
+        ret.extend(config.get_layer_names());
     }
 
-    Ok(ret)
+    ret
 }
 
-fn filter_xml(src_buf: &[u8], target: LayerTarget) -> Result<Option<Vec<u8>>, Box<dyn Error>>
+#[derive(Debug, Serialize, Deserialize)]
+struct ImageInfoConfig
 {
-    const TARGET_TAG: &[u8] = "g".as_bytes();
-    const TARGET_ATTR_KEY_GROUPMODE: &[u8] = "inkscape:groupmode".as_bytes();
-    const TARGET_ATTR_VAL_GROUPMODE: &[u8] = "layer".as_bytes();
-    const TARGET_ATTR_KEY_LABEL: &[u8] = "inkscape:label".as_bytes();
-
-    let mut writer = quick_xml::Writer::new(Cursor::new(Vec::<u8>::new()));
-
-    let mut found = false;
+    theme_name: Option<String>,
+    theme_description: Option<String>,
+    with_text_time_zone: Option<bool>,
+    with_text_date: Option<bool>,
+    with_text_time: Option<bool>,
+    with_text_segment: Option<bool>,
+    with_text_moon: Option<bool>,
+    with_text_sun: Option<bool>,
+    with_text_chrono: Option<bool>,
+    with_text_timer: Option<bool>,
+    with_text_calendar: Option<bool>,
+    with_text_digit: Option<bool>,
+    with_text_digit_transition: Option<bool>,
+    with_text_word_clock: Option<bool>,
+    word_clock_resolution: Option<u32>,
+    daylight_arc: Option<TimeDriver>,
+    daylight_arc_offset: Option<f64>,
+    daylight_arc_radius: Option<f64>,
+    daylight_arc_width: Option<f64>,
+    daylight_arc_color: Option<String>,
+    countdown_arc: Option<bool>,
+    countdown_arc_radius: Option<f64>,
+    countdown_arc_width: Option<f64>,
+    countdown_arc_color: Option<String>,
+    enable_rotate_center_circle: Option<bool>,
+    enable_update_region_every_time: Option<bool>,
+    hands: Option<Vec<ImageInfoConfigHand>>,
+    wheels: Option<Vec<ImageInfoConfigWheel>>,
+    sub_dials: Option<Vec<ImageInfoConfigSubDial>>,
+}
 
-    let mut depth = 0;
-    let mut depth_dis_output = -1;
+/*
+    a hand declared in the config layer
+
+    [[hands]]
+    layer = "gmt_handle"            # the layer of the hand. points at 12 o'clock
+    driver = "hour24"               # see TimeDriver
+    pivot = "gmt_center_circle"     # a layer with a circle. default = "center_circle"
+    smooth = true                   # default = true ( false for day_of_week, day_of_month, month )
+
+    start_angle = -120              # a retrograde hand. see HandRange
+    end_angle = 120
+    value_min = 0
+    value_max = 7
+    curve = "linear"
+    points = [[0, -120], [6, 120]]
+    snap_back = 1.0
+*/
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ImageInfoConfigHand
+{
+    layer: String,
+    driver: TimeDriver,
+    pivot: Option<String>,
+    smooth: Option<bool>,
+    #[serde(flatten)]
+    range: HandRange,
+}
 
-    let mut r_src = XmlInputReader::from_reader(&src_buf);
+/*
+    a wheel ( disc ) declared in the config layer. turns one step per unit of the driver
+
+    [[wheels]]
+    layer = "date_wheel"            # the wheel. drawn with the value 0 ( the 1st ) at the window
+    driver = "day_of_month"         # see TimeDriver
+    pivot = "date_wheel_center"     # a layer with a circle. default = "center_circle"
+    steps = 31                      # the number of the values on the wheel. default = the range of the driver
+    start_angle = 90                # the rotation at the value 0. default = 0
+    clockwise = false               # default = false ( the next value comes from the clockwise side )
+    window = "date_window_mask"     # a layer. the wheel is drawn only in the painted area of it. not drawn
+    aperture = "date_window"        # a layer drawn above the wheel
+    duration = 0.8                  # seconds of the move to the next value. default = 0.8
+*/
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ImageInfoConfigWheel
+{
+    layer: String,
+    driver: TimeDriver,
+    pivot: Option<String>,
+    steps: Option<u32>,
+    start_angle: Option<f64>,
+    clockwise: Option<bool>,
+    window: Option<String>,
+    aperture: Option<String>,
+    duration: Option<f64>,
+}
 
-    loop
-    {
-        let event = r_src.read_event();
+const WHEEL_DURATION_DEFAULT: f64 = 0.8;
 
-        match event
-        {
-            Ok(quick_xml::events::Event::Eof) => break,
+/*
+    a sub-dial declared in the config layer. a small dial with its own base, hand and pivot
 
-            Ok(evt) =>
-            {
-                match evt
-                {
-                    quick_xml::events::Event::Start(ref tag) =>
-                    {
-                        depth += 1;
+    [[sub_dials]]
+    name = "hour24"                     # the prefix of the layers
+    driver = "hour24"                   # see TimeDriver and ChronoDriver ( the stopwatch )
+    base = "hour24_base"                # default = "<name>_base". optional layer
+    handle = "hour24_handle"            # default = "<name>_handle". points at 12 o'clock
+    center_circle = "hour24_center"     # default = "<name>_center_circle". the pivot
+    smooth = true                       # default = true ( false for day_of_week, day_of_month, month )
 
-                        if depth == 2 && tag.name().as_ref() == TARGET_TAG
-                        {
-                            let mut output = false;
+    start_angle = -120                  # a retrograde hand. see HandRange
+    ...
 
-                            if let Ok(attr) = tag.try_get_attribute(TARGET_ATTR_KEY_GROUPMODE)
-                                && let Some(attr) = attr
-                            {
-                                if attr.value.as_ref() == TARGET_ATTR_VAL_GROUPMODE
-                                {
-                                    if let Ok(attr) = tag.try_get_attribute(TARGET_ATTR_KEY_LABEL)
-                                        && let Some(attr) = attr
-                                    {
-                                        if attr.value.as_ref() == target.to_string().as_bytes()
-                                        {
-                                            output = true;
-                                            found |= true;
-                                        }
-                                    }
-                                }
-                            }
+    The sub_second_base, sub_second_handle and sub_second_center_circle layers are the sub-dial
+    "sub_second" ( driver = "second_of_minute" ) without the declaration.
+    A sub-dial of second_of_minute replaces second_handle if "Enable sub second handle" is on.
+*/
 
-                            depth_dis_output = if output { -1 } else { depth };
-                        }
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum SubDialDriver
+{
+    Time(TimeDriver),
+    Chrono(ChronoDriver),
+}
 
-                        if depth_dis_output == -1
-                        {
-                            assert!(writer.write_event(evt).is_ok())
-                        }
-                    },
-                    quick_xml::events::Event::End(ref _tag) =>
-                    {
-                        if depth_dis_output == -1
-                        {
-                            assert!(writer.write_event(evt).is_ok())
-                        }
-                        else if depth == depth_dis_output
-                        {
-                            depth_dis_output = -1;
-                        }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ImageInfoConfigSubDial
+{
+    name: String,
+    driver: SubDialDriver,
+    base: Option<String>,
+    handle: Option<String>,
+    center_circle: Option<String>,
+    smooth: Option<bool>,
+    #[serde(flatten)]
+    range: HandRange,
+}
 
-                        depth -= 1;
-                    },
-                    _ =>
-                    {
-                        if depth_dis_output == -1
-                        {
-                            assert!(writer.write_event(evt.borrow()).is_ok())
-                        }
-                    },
-                }
-            },
+const SUB_DIAL_NAME_SUB_SECOND: &str = "sub_second";
 
-            Err(e) => return Err(Box::new(e)),
+impl ImageInfoConfigSubDial
+{
+    fn sub_second() -> Self
+    {
+        Self {
+            name: String::from(SUB_DIAL_NAME_SUB_SECOND),
+            driver: SubDialDriver::Time(TimeDriver::SecondOfMinute),
+            base: Some(LayerTarget::SubSecondBase.to_string()),
+            handle: Some(LayerTarget::SubSecondHandle.to_string()),
+            center_circle: Some(LayerTarget::SubSecondCenterCircle.to_string()),
+            smooth: None,
+            range: HandRange::default(),
         }
     }
 
-    if found
+    fn base_layer(&self) -> String
     {
-        let inner_buf = writer.into_inner().into_inner();
-        Ok(Some(inner_buf))
+        self.base.clone().unwrap_or_else(|| format!("{}_base", self.name))
     }
-    else
+
+    fn handle_layer(&self) -> String
     {
-        Ok(None)
+        self.handle.clone().unwrap_or_else(|| format!("{}_handle", self.name))
     }
-}
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ImageInfoConfig
-{
-    theme_name: Option<String>,
-    theme_description: Option<String>,
-    with_text_time_zone: Option<bool>,
-    with_text_date: Option<bool>,
-    with_text_time: Option<bool>,
-    with_text_segment: Option<bool>,
-    enable_rotate_center_circle: Option<bool>,
-    enable_update_region_every_time: Option<bool>,
+    fn center_circle_layer(&self) -> String
+    {
+        self.center_circle.clone().unwrap_or_else(|| format!("{}_center_circle", self.name))
+    }
 }
 
 impl ImageInfoConfig
@@ -761,6 +678,48 @@ impl ImageInfoConfig
 
         ret
     }
+
+    // the layers named in hands, wheels and sub_dials
+    fn get_layer_names(&self) -> Vec<String>
+    {
+        let mut ret = Vec::<String>::new();
+
+        for hand in self.hands.iter().flatten()
+        {
+            ret.push(hand.layer.clone());
+            ret.extend(hand.pivot.clone());
+        }
+
+        for wheel in self.wheels.iter().flatten()
+        {
+            ret.push(wheel.layer.clone());
+            ret.extend(wheel.pivot.clone());
+            ret.extend(wheel.window.clone());
+            ret.extend(wheel.aperture.clone());
+        }
+
+        for sub_dial in self.get_sub_dials()
+        {
+            ret.push(sub_dial.base_layer());
+            ret.push(sub_dial.handle_layer());
+            ret.push(sub_dial.center_circle_layer());
+        }
+
+        ret
+    }
+
+    // the sub-dials declared in the config and the sub-dial of the sub_second_xxx layers
+    fn get_sub_dials(&self) -> Vec<ImageInfoConfigSubDial>
+    {
+        let mut ret = self.sub_dials.clone().unwrap_or_default();
+
+        if !ret.iter().any(|x| x.name == SUB_DIAL_NAME_SUB_SECOND)
+        {
+            ret.insert(0, ImageInfoConfigSubDial::sub_second());
+        }
+
+        ret
+    }
 }
 
 impl ImageInfoConfig
@@ -774,8 +733,29 @@ impl ImageInfoConfig
             with_text_date: None,                  // = false
             with_text_time: None,                  // = false
             with_text_segment: None,               // = false
+            with_text_moon: None,                  // = false
+            with_text_sun: None,                   // = false
+            with_text_chrono: None,                // = false
+            with_text_timer: None,                 // = false
+            with_text_calendar: None,              // = false
+            with_text_digit: None,                 // = false
+            with_text_digit_transition: None,      // = false
+            with_text_word_clock: None,            // = false
+            word_clock_resolution: None,           // = 5. minutes. 5 or 1
+            daylight_arc: None,                    // hour24 or hour12. None = not drawn
+            daylight_arc_offset: None,             // = 0. degrees. the angle of 00:00
+            daylight_arc_radius: None,             // = 0.45 * the size of the viewBox
+            daylight_arc_width: None,              // = 0.05 * radius
+            daylight_arc_color: None,              // = "#ffc80080"
+            countdown_arc: None,                   // = true. false = not drawn. not drawn with countdown_handle
+            countdown_arc_radius: None,            // = 0.3 * the size of the viewBox
+            countdown_arc_width: None,             // = 0.2 * radius
+            countdown_arc_color: None,             // = "#ff404080"
             enable_rotate_center_circle: None,     // = false
             enable_update_region_every_time: None, // = false
+            hands: None,
+            wheels: None,
+            sub_dials: None,
         }
     }
 
@@ -801,14 +781,54 @@ impl ImageInfoConfig
             self.with_text_segment = Some(false);
         }
 
-        if self.enable_rotate_center_circle.is_none()
+        if self.with_text_moon.is_none()
         {
-            self.enable_rotate_center_circle = Some(false);
+            self.with_text_moon = Some(false);
         }
 
-        if self.enable_update_region_every_time.is_none()
+        if self.with_text_sun.is_none()
         {
-            self.enable_update_region_every_time = Some(false);
+            self.with_text_sun = Some(false);
+        }
+
+        if self.with_text_chrono.is_none()
+        {
+            self.with_text_chrono = Some(false);
+        }
+
+        if self.with_text_timer.is_none()
+        {
+            self.with_text_timer = Some(false);
+        }
+
+        if self.with_text_calendar.is_none()
+        {
+            self.with_text_calendar = Some(false);
+        }
+
+        if self.with_text_digit.is_none()
+        {
+            self.with_text_digit = Some(false);
+        }
+
+        if self.with_text_digit_transition.is_none()
+        {
+            self.with_text_digit_transition = Some(false);
+        }
+
+        if self.with_text_word_clock.is_none()
+        {
+            self.with_text_word_clock = Some(false);
+        }
+
+        if self.enable_rotate_center_circle.is_none()
+        {
+            self.enable_rotate_center_circle = Some(false);
+        }
+
+        if self.enable_update_region_every_time.is_none()
+        {
+            self.enable_update_region_every_time = Some(false);
         }
     }
 }
@@ -824,27 +844,85 @@ struct ImageInfo
     bytes_short_handle:             Option<Vec<u8>>,
     bytes_second_handle:            Option<Vec<u8>>,
     bytes_center_circle:            Option<Vec<u8>>,
-    bytes_sub_second_base:          Option<Vec<u8>>,
-    bytes_sub_second_handle:        Option<Vec<u8>>,
-    bytes_sub_second_center_circle: Option<Vec<u8>>,
+    bytes_moon_phase_disc:          Option<Vec<u8>>,
+    bytes_moon_phase_aperture:      Option<Vec<u8>>,
+    bytes_chrono_second_handle:     Option<Vec<u8>>,
+    bytes_chrono_minute_handle:     Option<Vec<u8>>,
+    bytes_chrono_hour_handle:       Option<Vec<u8>>,
+    bytes_countdown_handle:         Option<Vec<u8>>,
+    bytes_alarm_handle:             Option<Vec<u8>>,
+    bytes_gmt_handle:               Option<Vec<u8>>,
 
     svgh_base:                     Option<SvgHandle>,
     svgh_long_handle:              Option<SvgHandle>,
     svgh_short_handle:             Option<SvgHandle>,
     svgh_second_handle:            Option<SvgHandle>,
     svgh_center_circle:            Option<SvgHandle>,
-    svgh_sub_second_base:          Option<SvgHandle>,
-    svgh_sub_second_handle:        Option<SvgHandle>,
-    svgh_sub_second_center_circle: Option<SvgHandle>,
+    svgh_moon_phase_disc:          Option<SvgHandle>,
+    svgh_moon_phase_aperture:      Option<SvgHandle>,
+    svgh_chrono_second_handle:     Option<SvgHandle>,
+    svgh_chrono_minute_handle:     Option<SvgHandle>,
+    svgh_chrono_hour_handle:       Option<SvgHandle>,
+    svgh_countdown_handle:         Option<SvgHandle>,
+    svgh_alarm_handle:             Option<SvgHandle>,
+    svgh_gmt_handle:               Option<SvgHandle>,
 
     template_base_text:             Option<Template>,
+    digit_transition_positions:     Vec<&'static str>, // the digit positions of {{digit_xxx_from}}, ... in base_text
 
     center:            DVec2,
-    center_sub_second: DVec2,
+    center_moon_phase: DVec2,
+
+    hands:     Vec<ImageInfoHand>,
+    wheels:    Vec<ImageInfoWheel>,
+    sub_dials: Vec<ImageInfoSubDial>,
 
     config: ImageInfoConfig,
 }
 
+struct ImageInfoHand
+{
+    driver: TimeDriver,
+    smooth: bool,
+    range:  HandRange,
+    center: DVec2,
+    svgh:   SvgHandle,
+}
+
+struct ImageInfoWheel
+{
+    driver:        TimeDriver,
+    steps:         Option<u32>,
+    start_angle:   f64,
+    clockwise:     bool,
+    duration:      f64,
+    center:        DVec2,
+    svgh:          SvgHandle,
+    svgh_window:   Option<SvgHandle>,
+    svgh_aperture: Option<SvgHandle>,
+}
+
+struct ImageInfoSubDial
+{
+    name:               String,
+    driver:             SubDialDriver,
+    smooth:             bool,
+    range:              HandRange,
+    center:             DVec2,
+    svgh_base:          Option<SvgHandle>,
+    svgh_handle:        SvgHandle,
+    svgh_center_circle: Option<SvgHandle>,
+}
+
+impl ImageInfoSubDial
+{
+    // the running seconds of the sub_second_xxx layers. drawn instead of second_handle
+    fn is_second(&self) -> bool
+    {
+        self.name == SUB_DIAL_NAME_SUB_SECOND
+    }
+}
+
 impl ImageInfo
 {
     const fn new() -> Self
@@ -859,765 +937,2128 @@ impl ImageInfo
             bytes_short_handle:             None,
             bytes_second_handle:            None,
             bytes_center_circle:            None,
-            bytes_sub_second_base:          None,
-            bytes_sub_second_handle:        None,
-            bytes_sub_second_center_circle: None,
+            bytes_moon_phase_disc:          None,
+            bytes_moon_phase_aperture:      None,
+            bytes_chrono_second_handle:     None,
+            bytes_chrono_minute_handle:     None,
+            bytes_chrono_hour_handle:       None,
+            bytes_countdown_handle:         None,
+            bytes_alarm_handle:             None,
+            bytes_gmt_handle:               None,
 
             svgh_base:                     None,
             svgh_long_handle:              None,
             svgh_short_handle:             None,
             svgh_second_handle:            None,
             svgh_center_circle:            None,
-            svgh_sub_second_base:          None,
-            svgh_sub_second_handle:        None,
-            svgh_sub_second_center_circle: None,
+            svgh_moon_phase_disc:          None,
+            svgh_moon_phase_aperture:      None,
+            svgh_chrono_second_handle:     None,
+            svgh_chrono_minute_handle:     None,
+            svgh_chrono_hour_handle:       None,
+            svgh_countdown_handle:         None,
+            svgh_alarm_handle:             None,
+            svgh_gmt_handle:               None,
 
             template_base_text:             None,
+            digit_transition_positions:     Vec::new(),
 
             center:            DVec2::ZERO,
-            center_sub_second: DVec2::ZERO,
+            center_moon_phase: DVec2::ZERO,
+
+            hands:     Vec::new(),
+            wheels:    Vec::new(),
+            sub_dials: Vec::new(),
 
             config: ImageInfoConfig::new(),
         }
     }
+
+    // the digit positions to start the transition. none without with_text_digit_transition
+    fn digit_transition_positions(&self) -> &[&'static str]
+    {
+        if self.config.with_text_digit_transition.is_some_and(|x| x)
+        {
+            &self.digit_transition_positions
+        }
+        else
+        {
+            &[]
+        }
+    }
+
+    // the empty image to draw the theme error on
+    fn new_for_error() -> Self
+    {
+        let mut ret = Self::new();
+
+        ret.sz = IVec2::splat(THEME_ERROR_IMAGE_SIZE);
+        ret.viewbox_sz = ret.sz.as_dvec2();
+        ret.center = ret.viewbox_sz / 2.0;
+
+        ret
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ThemeSource
+{
+    Builtin(&'static [u8]),
+    File(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+struct ThemeEntry
+{
+    source:      ThemeSource,
+    name:        Option<String>,
+    description: Option<String>,
 }
 
-fn load_theme(theme: AppInfoTheme, theme_custom: Option<String>) -> Option<ImageInfo>
+impl ThemeEntry
 {
-    let src_buf: Option<Vec<u8>> = match theme
+    fn new(source: ThemeSource) -> Self
     {
-        AppInfoTheme::Custom =>
+        Self {
+            source,
+            name: None,
+            description: None,
+        }
+    }
+
+    fn load_names(&mut self)
+    {
+        if let Ok(src_buf) = self.read()
+        {
+            let config = load_xml_config(&src_buf);
+
+            self.name = config.get_theme_name();
+            self.description = config.get_theme_description();
+        }
+    }
+
+    fn read(&self) -> Result<Vec<u8>, Box<dyn Error>>
+    {
+        match &self.source
         {
-            if let Some(theme_custom) = theme_custom
+            ThemeSource::Builtin(bytes) => Ok(bytes.to_vec()),
+            ThemeSource::File(path) =>
             {
                 let mut src_buf = Vec::<u8>::new();
 
-                if let Ok(mut src) = File::open(theme_custom)
+                match File::open(path).and_then(|mut src| src.read_to_end(&mut src_buf))
                 {
-                    if let Ok(_) = src.read_to_end(&mut src_buf)
-                    {
-                        Some(src_buf)
-                    }
-                    else
+                    Ok(_) => Ok(src_buf),
+                    Err(err) =>
                     {
-                        None
-                    }
-                }
-                else
-                {
-                    None
+                        error!("{:?} : {}", path, err);
+                        Err(format!("{} : {}", path.to_string_lossy(), err).into())
+                    },
                 }
-            }
-            else
-            {
-                None
-            }
-        },
-        _ =>
+            },
+        }
+    }
+}
+
+fn theme_id_from_path(path: &std::path::Path) -> Option<String>
+{
+    if let Some(ext) = path.extension()
+        && ext.to_string_lossy().to_lowercase() == "svg"
+        && let Some(stem) = path.file_stem()
+    {
+        Some(stem.to_string_lossy().to_string())
+    }
+    else
+    {
+        None
+    }
+}
+
+fn get_theme_dirs(theme_dirs: &Vec<String>) -> Vec<PathBuf>
+{
+    // lowest priority first. a later directory overrides a theme with the same id.
+
+    let mut ret = Vec::<PathBuf>::new();
+
+    if let Ok(exe) = std::env::current_exe()
+        && let Some(dir) = exe.parent()
+    {
+        ret.push(dir.join(DIR_THEME_EXE));
+    }
+
+    for dir in glib::system_data_dirs().iter().rev()
+    {
+        ret.push(dir.join(DIR_THEME_SYSTEM));
+    }
+
+    ret.push(glib::user_data_dir().join(DIR_THEME_USER));
+
+    for dir in theme_dirs
+    {
+        ret.push(PathBuf::from(dir));
+    }
+
+    ret
+}
+
+fn theme_id_custom(path: &str) -> String
+{
+    format!("{}{}", THEME_ID_CUSTOM_PREFIX, path)
+}
+
+fn is_theme_id_custom(id: &str) -> bool
+{
+    id.starts_with(THEME_ID_CUSTOM_PREFIX)
+}
+
+fn scan_themes(theme_dirs: &Vec<String>, theme_customs: &Vec<String>)
+    -> LinkedHashMap<String, ThemeEntry>
+{
+    let mut ret = LinkedHashMap::<String, ThemeEntry>::new();
+
+    let mut add = |id: String, source: ThemeSource| {
+        if let Some(entry) = ret.get_mut(&id)
         {
-            let tmp_src_buf: Option<Vec<u8>> = if ENABLE_FILE_INCLUDE
+            *entry = ThemeEntry::new(source);
+        }
+        else
+        {
+            ret.insert(id, ThemeEntry::new(source));
+        }
+    };
+
+    for (bytes, file) in BUILTIN_THEMES
+    {
+        if let Some(id) = theme_id_from_path(std::path::Path::new(file))
+        {
+            if ENABLE_FILE_INCLUDE
             {
-                match theme
-                {
-                    AppInfoTheme::Theme1 => Some(INCLUDE_BYTES_CLOCL_THEME_1_SVG.to_vec()),
-                    AppInfoTheme::Theme2 => Some(INCLUDE_BYTES_CLOCL_THEME_2_SVG.to_vec()),
-                    AppInfoTheme::Theme3 => Some(INCLUDE_BYTES_CLOCL_THEME_3_SVG.to_vec()),
-                    AppInfoTheme::Theme4 => Some(INCLUDE_BYTES_CLOCL_THEME_4_SVG.to_vec()),
-                    AppInfoTheme::Theme5 => Some(INCLUDE_BYTES_CLOCL_THEME_5_SVG.to_vec()),
-                    AppInfoTheme::Theme6 => Some(INCLUDE_BYTES_CLOCL_THEME_6_SVG.to_vec()),
-                    AppInfoTheme::Theme7 => Some(INCLUDE_BYTES_CLOCL_THEME_7_SVG.to_vec()),
-                    AppInfoTheme::Theme8 => Some(INCLUDE_BYTES_CLOCL_THEME_8_SVG.to_vec()),
-                    _ => None,
-                }
+                add(id, ThemeSource::Builtin(bytes));
             }
-            else
+            else if std::path::Path::new(file).is_file()
             {
-                None
-            };
+                add(id, ThemeSource::File(PathBuf::from(file)));
+            }
+        }
+    }
 
-            if tmp_src_buf.is_some()
+    for dir in get_theme_dirs(theme_dirs)
+    {
+        let Ok(read_dir) = std::fs::read_dir(&dir)
+        else
+        {
+            continue;
+        };
+
+        debug!("scan_themes: {:?}", dir);
+
+        let mut paths: Vec<PathBuf> = read_dir
+            .filter_map(|x| x.ok())
+            .map(|x| x.path())
+            .filter(|x| x.is_file())
+            .collect();
+
+        paths.sort();
+
+        for path in paths
+        {
+            if let Some(id) = theme_id_from_path(&path)
             {
-                tmp_src_buf
+                add(id, ThemeSource::File(path));
             }
-            else
-            {
-                /* load from file */
-                let source = match theme
-                {
-                    AppInfoTheme::Theme1 => FILE_CLOCL_THEME_1_SVG,
-                    AppInfoTheme::Theme2 => FILE_CLOCL_THEME_2_SVG,
-                    AppInfoTheme::Theme3 => FILE_CLOCL_THEME_3_SVG,
-                    AppInfoTheme::Theme4 => FILE_CLOCL_THEME_4_SVG,
-                    AppInfoTheme::Theme5 => FILE_CLOCL_THEME_5_SVG,
-                    AppInfoTheme::Theme6 => FILE_CLOCL_THEME_6_SVG,
-                    AppInfoTheme::Theme7 => FILE_CLOCL_THEME_7_SVG,
-                    AppInfoTheme::Theme8 => FILE_CLOCL_THEME_8_SVG,
-                    _ => "",
-                };
+        }
+    }
 
-                let mut src_buf = Vec::<u8>::new();
+    for theme_custom in theme_customs
+    {
+        add(
+            theme_id_custom(theme_custom),
+            ThemeSource::File(PathBuf::from(theme_custom)),
+        );
+    }
 
-                if let Ok(mut src) = File::open(source)
-                {
-                    if let Ok(_) = src.read_to_end(&mut src_buf)
-                    {
-                        Some(src_buf)
-                    }
-                    else
-                    {
-                        None
-                    }
-                }
-                else
-                {
-                    None
-                }
-            }
-        },
-    };
+    for (_, entry) in ret.iter_mut()
+    {
+        entry.load_names();
+    }
+
+    ret
+}
+
+fn load_theme(entry: &ThemeEntry) -> Result<ImageInfo, Box<dyn Error>>
+{
+    load_xml(&entry.read()?)
+}
 
-    if let Some(src_buf) = src_buf
+fn load_xml_config(src_buf: &Vec<u8>) -> ImageInfoConfig
+{
+    if let Ok(Some(src_xml)) = filter_xml(src_buf, LayerTarget::Config)
+        && let Ok(config) = parse_xml_config(&src_xml)
     {
-        Some(load_xml(&src_buf))
+        config
     }
     else
     {
-        None
+        ImageInfoConfig::new()
     }
 }
 
-fn load_xml(src_buf: &Vec<u8>) -> ImageInfo
-{
-    let src_base = filter_xml(src_buf, LayerTarget::Base);
-    let src_base_text = filter_xml(src_buf, LayerTarget::BaseText);
-    let src_long_handle = filter_xml(src_buf, LayerTarget::LongHandle);
-    let src_short_handle = filter_xml(src_buf, LayerTarget::ShortHandle);
-    let src_second_handle = filter_xml(src_buf, LayerTarget::SecondHandle);
-    let src_center_circle = filter_xml(src_buf, LayerTarget::CenterCircle);
-    let src_sub_second_base = filter_xml(src_buf, LayerTarget::SubSecondBase);
-    let src_sub_second_handle = filter_xml(src_buf, LayerTarget::SubSecondHandle);
-    let src_sub_second_center_circle = filter_xml(src_buf, LayerTarget::SubSecondCenterCircle);
-    let src_config = filter_xml(src_buf, LayerTarget::Config);
+// {{digit_(pos)_(from|to|progress)...}} of base_text. see draw_watch
+static RE_DIGIT_TRANSITION: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"(?i)^digit_(hh|hl|mh|ml|sh|sl|yr1|yr2|yr3|yr4|moh|mol|dah|dal|dy1|dy2|dy3)_(from|to|progress)(?:_([0-9])|_seg_([a-g]))?$").unwrap()
+});
 
-    let fn_make_svg_handle = |src_xml: &Vec<u8>| {
+fn load_xml(src_buf: &Vec<u8>) -> Result<ImageInfo, Box<dyn Error>>
+{
+    let src_base = filter_xml(src_buf, LayerTarget::Base)?;
+    let src_base_text = filter_xml(src_buf, LayerTarget::BaseText)?;
+    let src_long_handle = filter_xml(src_buf, LayerTarget::LongHandle)?;
+    let src_short_handle = filter_xml(src_buf, LayerTarget::ShortHandle)?;
+    let src_second_handle = filter_xml(src_buf, LayerTarget::SecondHandle)?;
+    let src_center_circle = filter_xml(src_buf, LayerTarget::CenterCircle)?;
+    let src_moon_phase_disc = filter_xml(src_buf, LayerTarget::MoonPhaseDisc)?;
+    let src_moon_phase_center_circle = filter_xml(src_buf, LayerTarget::MoonPhaseCenterCircle)?;
+    let src_moon_phase_aperture = filter_xml(src_buf, LayerTarget::MoonPhaseAperture)?;
+    let src_chrono_second_handle = filter_xml(src_buf, LayerTarget::ChronoSecondHandle)?;
+    let src_chrono_minute_handle = filter_xml(src_buf, LayerTarget::ChronoMinuteHandle)?;
+    let src_chrono_hour_handle = filter_xml(src_buf, LayerTarget::ChronoHourHandle)?;
+    let src_countdown_handle = filter_xml(src_buf, LayerTarget::CountdownHandle)?;
+    let src_alarm_handle = filter_xml(src_buf, LayerTarget::AlarmHandle)?;
+    let src_gmt_handle = filter_xml(src_buf, LayerTarget::GmtHandle)?;
+    let src_config = filter_xml(src_buf, LayerTarget::Config)?;
+
+    let fn_make_svg_handle = |src_xml: &Vec<u8>| -> Result<Option<SvgHandle>, Box<dyn Error>> {
         let svg_stream = gtk::gio::MemoryInputStream::from_bytes(&gtk::glib::Bytes::from(src_xml));
 
-        Some(
-            rsvg::Loader::new()
-                .read_stream(
-                    &svg_stream,
-                    None::<&gtk::gio::File>,
-                    None::<&gtk::gio::Cancellable>,
-                )
-                .unwrap(),
-        )
+        Ok(Some(rsvg::Loader::new().read_stream(
+            &svg_stream,
+            None::<&gtk::gio::File>,
+            None::<&gtk::gio::Cancellable>,
+        )?))
     };
 
     let mut ret = ImageInfo::new();
 
-    if let Ok(Some(src_xml)) = src_config
+    if let Some(src_xml) = src_config
     {
-        if let Ok(config) = parse_xml_config(&src_xml)
-        {
-            debug!("config load   {:?}", config);
+        let mut config = parse_xml_config(&src_xml)?;
 
-            let mut config = config;
-            config.update_default();
+        debug!("config load   {:?}", config);
 
-            debug!("config update {:?}", config);
+        config.update_default();
 
-            ret.config = config;
-        }
+        debug!("config update {:?}", config);
+
+        ret.config = config;
     }
 
-    if let Ok(Some(src_xml)) = src_base
+    if let Some(src_xml) = src_base
     {
-        if let Ok(result) = parse_xml_sz_and_vbox(&src_xml)
-        {
-            ret.sz = result.0;
-            ret.viewbox_xy = result.1;
-            ret.viewbox_sz = result.2;
-        }
+        let result = parse_xml_sz_and_vbox(&src_xml)?;
 
-        ret.svgh_base = fn_make_svg_handle(&src_xml);
+        ret.sz = result.0;
+        ret.viewbox_xy = result.1;
+        ret.viewbox_sz = result.2;
+
+        ret.svgh_base = fn_make_svg_handle(&src_xml)?;
         ret.bytes_base = Some(src_xml);
     }
 
-    if let Ok(Some(src_xml)) = src_base_text
+    if let Some(src_xml) = src_base_text
     {
         let template = svgclock_rs::minitemplate::parse( Cursor::new(&src_xml) );
 
         if let Ok( template ) = template {
+            // the digit transition starts only when one of these positions changes
+            let names = template.get_var_names();
+
+            ret.digit_transition_positions = DIGIT_POSITIONS
+                .iter()
+                .copied()
+                .filter(|pos| {
+                    names.iter().any(|kw| {
+                        RE_DIGIT_TRANSITION.captures(kw).is_some_and(|caps| caps[1].eq_ignore_ascii_case(pos))
+                    })
+                })
+                .collect();
+
             ret.template_base_text = Some( template );
         }
         else {
             let err = template.unwrap_err();
-            error!( "base_text : {}", err );
+            return Err( format!( "base_text : {}", err ).into() );
         }
     }
 
-    if let Ok(Some(src_xml)) = src_long_handle
+    if let Some(src_xml) = src_long_handle
     {
-        ret.svgh_long_handle = fn_make_svg_handle(&src_xml);
+        ret.svgh_long_handle = fn_make_svg_handle(&src_xml)?;
         ret.bytes_long_handle = Some(src_xml);
     }
 
-    if let Ok(Some(src_xml)) = src_short_handle
+    if let Some(src_xml) = src_short_handle
     {
-        ret.svgh_short_handle = fn_make_svg_handle(&src_xml);
+        ret.svgh_short_handle = fn_make_svg_handle(&src_xml)?;
         ret.bytes_short_handle = Some(src_xml);
     }
 
-    if let Ok(Some(src_xml)) = src_second_handle
+    if let Some(src_xml) = src_second_handle
     {
-        ret.svgh_second_handle = fn_make_svg_handle(&src_xml);
+        ret.svgh_second_handle = fn_make_svg_handle(&src_xml)?;
         ret.bytes_second_handle = Some(src_xml);
     }
 
-    if let Ok(Some(src_xml)) = src_center_circle
+    if let Some(src_xml) = src_center_circle
     {
-        if let Ok(center) = parse_xml_center(&src_xml, LayerTarget::CenterCircle)
-        {
-            ret.center = center;
-        }
+        ret.center = parse_xml_center(&src_xml, LayerTarget::CenterCircle)?.unwrap_or(DVec2::ZERO);
 
         debug!("ret.center: {:?}", ret.center);
 
-        ret.svgh_center_circle = fn_make_svg_handle(&src_xml);
+        ret.svgh_center_circle = fn_make_svg_handle(&src_xml)?;
         ret.bytes_center_circle = Some(src_xml);
     }
 
-    if let Ok(Some(src_xml)) = src_sub_second_base
+    if let Some(src_xml) = src_moon_phase_disc
     {
-        ret.svgh_sub_second_base = fn_make_svg_handle(&src_xml);
-        ret.bytes_sub_second_base = Some(src_xml);
+        ret.svgh_moon_phase_disc = fn_make_svg_handle(&src_xml)?;
+        ret.bytes_moon_phase_disc = Some(src_xml);
     }
 
-    if let Ok(Some(src_xml)) = src_sub_second_handle
+    // the pivot of moon_phase_disc. not drawn
+    ret.center_moon_phase = match src_moon_phase_center_circle
     {
-        ret.svgh_sub_second_handle = fn_make_svg_handle(&src_xml);
-        ret.bytes_sub_second_handle = Some(src_xml);
-    }
+        Some(src_xml) => parse_xml_center(&src_xml, LayerTarget::MoonPhaseCenterCircle)?.unwrap_or(ret.center),
+        None => ret.center,
+    };
 
-    if let Ok(Some(src_xml)) = src_sub_second_center_circle
+    if let Some(src_xml) = src_moon_phase_aperture
     {
-        if let Ok(center) = parse_xml_center(&src_xml, LayerTarget::SubSecondCenterCircle)
-        {
-            ret.center_sub_second = center;
-        }
+        ret.svgh_moon_phase_aperture = fn_make_svg_handle(&src_xml)?;
+        ret.bytes_moon_phase_aperture = Some(src_xml);
+    }
 
-        debug!("ret.center: {:?}", ret.center);
+    if let Some(src_xml) = src_chrono_second_handle
+    {
+        ret.svgh_chrono_second_handle = fn_make_svg_handle(&src_xml)?;
+        ret.bytes_chrono_second_handle = Some(src_xml);
+    }
 
-        ret.svgh_sub_second_center_circle = fn_make_svg_handle(&src_xml);
-        ret.bytes_sub_second_center_circle = Some(src_xml);
+    if let Some(src_xml) = src_chrono_minute_handle
+    {
+        ret.svgh_chrono_minute_handle = fn_make_svg_handle(&src_xml)?;
+        ret.bytes_chrono_minute_handle = Some(src_xml);
     }
 
-    ret
-}
+    if let Some(src_xml) = src_chrono_hour_handle
+    {
+        ret.svgh_chrono_hour_handle = fn_make_svg_handle(&src_xml)?;
+        ret.bytes_chrono_hour_handle = Some(src_xml);
+    }
 
-fn load_logo() -> Option<Pixbuf>
-{
-    if ENABLE_FILE_INCLUDE
+    if let Some(src_xml) = src_countdown_handle
     {
-        let mut src_buf = &INCLUDE_BYTES_LOGO_PNG[..];
-        let surface = ImageSurface::create_from_png(&mut src_buf).unwrap();
-        return gdk::pixbuf_get_from_surface(&surface, 0, 0, surface.width(), surface.height());
+        ret.svgh_countdown_handle = fn_make_svg_handle(&src_xml)?;
+        ret.bytes_countdown_handle = Some(src_xml);
     }
-    else
+
+    if let Some(src_xml) = src_alarm_handle
     {
-        // load logo
-        let mut src_buf = Vec::<u8>::new();
+        ret.svgh_alarm_handle = fn_make_svg_handle(&src_xml)?;
+        ret.bytes_alarm_handle = Some(src_xml);
+    }
 
-        let mut src = File::open(FILE_LOGO_PNG).unwrap();
-        src.read_to_end(&mut src_buf).unwrap();
+    if let Some(src_xml) = src_gmt_handle
+    {
+        ret.svgh_gmt_handle = fn_make_svg_handle(&src_xml)?;
+        ret.bytes_gmt_handle = Some(src_xml);
+    }
 
-        if FILE_LOGO_PNG.ends_with(".svg")
+    for hand in ret.config.hands.clone().unwrap_or_default()
+    {
+        let Some(src_xml) = filter_xml_layer(src_buf, &hand.layer)?
+        else
         {
-            if let Ok(result) = parse_xml_sz_and_vbox(&src_buf)
-            {
-                let sz = result.0;
-                let surface = ImageSurface::create(Format::ARgb32, sz.x, sz.y).unwrap();
+            error!("hands : layer `{}` not found", hand.layer);
+            continue;
+        };
 
-                {
-                    let svg_stream =
-                        gtk::gio::MemoryInputStream::from_bytes(&gtk::glib::Bytes::from(&src_buf));
+        let center = load_xml_pivot(src_buf, &ret, hand.pivot.as_deref(), &hand.layer)?;
 
-                    let svg_handle = rsvg::Loader::new()
-                        .read_stream(
-                            &svg_stream,
-                            None::<&gtk::gio::File>,
-                            None::<&gtk::gio::Cancellable>,
-                        )
-                        .unwrap();
+        debug!("hand {} {:?} center: {:?}", hand.layer, hand.driver, center);
 
-                    let cctx = Context::new(&surface).unwrap();
-                    let viewport = Rectangle::new(0.0, 0.0, sz.x as f64, sz.y as f64);
+        if let Some(svgh) = fn_make_svg_handle(&src_xml)?
+        {
+            ret.hands.push(ImageInfoHand {
+                driver: hand.driver,
+                smooth: hand.smooth.unwrap_or(hand.driver.is_smooth_default()),
+                range: hand.range,
+                center,
+                svgh,
+            });
+        }
+    }
 
-                    let svg_renderer = rsvg::CairoRenderer::new(&svg_handle);
-                    svg_renderer.render_document(&cctx, &viewport).unwrap();
-                }
+    for wheel in ret.config.wheels.clone().unwrap_or_default()
+    {
+        let Some(src_xml) = filter_xml_layer(src_buf, &wheel.layer)?
+        else
+        {
+            error!("wheels : layer `{}` not found", wheel.layer);
+            continue;
+        };
 
-                return gdk::pixbuf_get_from_surface(
-                    &surface,
-                    0,
-                    0,
-                    surface.width(),
-                    surface.height(),
-                );
+        let center = load_xml_pivot(src_buf, &ret, wheel.pivot.as_deref(), &wheel.layer)?;
+
+        debug!("wheel {} {:?} center: {:?}", wheel.layer, wheel.driver, center);
+
+        let fn_make_svg_handle_opt = |name: &Option<String>| -> Result<Option<SvgHandle>, Box<dyn Error>> {
+            match name
+            {
+                Some(x) => match filter_xml_layer(src_buf, x)?
+                {
+                    Some(src_xml) => fn_make_svg_handle(&src_xml),
+                    None =>
+                    {
+                        error!("wheels : layer `{}` of `{}` not found", x, wheel.layer);
+                        Ok(None)
+                    },
+                },
+                None => Ok(None),
             }
+        };
+
+        let svgh_window = fn_make_svg_handle_opt(&wheel.window)?;
+        let svgh_aperture = fn_make_svg_handle_opt(&wheel.aperture)?;
+
+        if let Some(svgh) = fn_make_svg_handle(&src_xml)?
+        {
+            ret.wheels.push(ImageInfoWheel {
+                driver: wheel.driver,
+                steps: wheel.steps.filter(|x| *x > 0),
+                start_angle: wheel.start_angle.unwrap_or(0.0),
+                clockwise: wheel.clockwise.unwrap_or(false),
+                duration: wheel.duration.unwrap_or(WHEEL_DURATION_DEFAULT),
+                center,
+                svgh,
+                svgh_window,
+                svgh_aperture,
+            });
         }
-        else if FILE_LOGO_PNG.ends_with(".png")
+    }
+
+    for sub_dial in ret.config.get_sub_dials()
+    {
+        let Some(src_xml) = filter_xml_layer(src_buf, &sub_dial.handle_layer())?
+        else
         {
-            let mut src_buf = &src_buf[..]; // with io.Read Trait
+            // the sub_second_xxx layers are optional
 
-            let surface = ImageSurface::create_from_png(&mut src_buf).unwrap();
+            if ret.config.sub_dials.iter().flatten().any(|x| x.name == sub_dial.name)
+            {
+                error!("sub_dials : layer `{}` of `{}` not found", sub_dial.handle_layer(), sub_dial.name);
+            }
+            continue;
+        };
 
-            return gdk::pixbuf_get_from_surface(&surface, 0, 0, surface.width(), surface.height());
+        let center = load_xml_pivot(src_buf, &ret, Some(&sub_dial.center_circle_layer()), &sub_dial.handle_layer())?;
+
+        debug!("sub_dial {} {:?} center: {:?}", sub_dial.name, sub_dial.driver, center);
+
+        let svgh_base = match filter_xml_layer(src_buf, &sub_dial.base_layer())?
+        {
+            Some(src_xml) => fn_make_svg_handle(&src_xml)?,
+            None => None,
+        };
+
+        let svgh_center_circle = match filter_xml_layer(src_buf, &sub_dial.center_circle_layer())?
+        {
+            Some(src_xml) => fn_make_svg_handle(&src_xml)?,
+            None => None,
+        };
+
+        if let Some(svgh_handle) = fn_make_svg_handle(&src_xml)?
+        {
+            ret.sub_dials.push(ImageInfoSubDial {
+                name: sub_dial.name.clone(),
+                driver: sub_dial.driver,
+                smooth: sub_dial.smooth.unwrap_or(match sub_dial.driver
+                {
+                    SubDialDriver::Time(x) => x.is_smooth_default(),
+                    SubDialDriver::Chrono(_) => true,
+                }),
+                range: sub_dial.range,
+                center,
+                svgh_base,
+                svgh_handle,
+                svgh_center_circle,
+            });
         }
     }
 
-    None
+    Ok(ret)
 }
 
-#[derive(
-    Debug,
-    PartialEq,
-    Eq,
-    Hash,
-    strum::EnumString,
-    strum::Display,
-    strum::EnumIter,
-    Copy,
-    Clone,
-    Serialize,
-    Deserialize,
-)]
-enum AppInfoTheme
+fn load_xml_pivot(
+    src_buf: &[u8],
+    image_info: &ImageInfo,
+    pivot: Option<&str>,
+    layer: &str,
+) -> Result<DVec2, Box<dyn Error>>
+{
+    // the center of the rotation of the layers declared in the config
+
+    let ret = match pivot
+    {
+        None => image_info.center,
+        Some(x) if x == LayerTarget::CenterCircle.to_string() => image_info.center,
+        Some(x) =>
+        {
+            let center = match filter_xml_layer(src_buf, x)?
+            {
+                Some(src_pivot) => parse_xml_center_layer(&src_pivot, x)?,
+                None => None,
+            };
+
+            center.unwrap_or_else(|| {
+                error!("pivot `{}` of `{}` has no circle", x, layer);
+                image_info.center
+            })
+        },
+    };
+
+    Ok(ret)
+}
+
+fn byte_pos_to_line_col(src_buf: &[u8], pos: usize) -> (usize, usize)
 {
-    Theme1,
-    Theme2,
-    Theme3,
-    Theme4,
-    Theme5,
-    Theme6,
-    Theme7,
-    Theme8,
-    Custom,
+    let head = &src_buf[.. pos.min(src_buf.len())];
+
+    let line = head.iter().filter(|&&x| x == b'\n').count() + 1;
+    let col = match head.iter().rposition(|&x| x == b'\n')
+    {
+        Some(x) => pos - x,
+        None => pos + 1,
+    };
+
+    (line, col)
 }
 
-#[derive(
-    Debug,
-    PartialEq,
-    strum::EnumString,
-    strum::Display,
-    strum::EnumIter,
-    Copy,
-    Clone,
-    Serialize,
-    Deserialize,
-)]
-enum AppInfoFormatDate
+fn find_layer_line(src_buf: &[u8], target: LayerTarget) -> Option<usize>
 {
-    DtFmt1,
-    DtFmt2,
-    DtFmt3,
-    DtFmt4,
-    DtCustom,
+    find_layer_line_name(src_buf, &target.to_string())
 }
 
-impl AppInfoFormatDate
+fn find_layer_line_name(src_buf: &[u8], name: &str) -> Option<usize>
 {
-    fn format_str(&self) -> (&str, &str)
+    find_layer_pos_name(src_buf, name).map(|x| byte_pos_to_line_col(src_buf, x).0)
+}
+
+// the byte position of the start tag of the layer
+fn find_layer_pos_name(src_buf: &[u8], name: &str) -> Option<usize>
+{
+    let ns_keys = get_layer_ns_keys(src_buf);
+
+    let mut r_src = XmlInputReader::from_reader(&src_buf);
+
+    loop
     {
-        // see https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+        let pos = r_src.buffer_position() as usize;
 
-        match self
+        match r_src.read_event()
         {
-            Self::DtFmt1 =>
-            {
-                (
-                    "%F", /* Year-month-day format (ISO 8601). Same as %Y-%m-%d. */
-                    "Year-month-day (e.g., 2025-10-31)",
-                )
-            },
-            Self::DtFmt2 =>
+            Ok(quick_xml::events::Event::Eof) | Err(_) => break,
+            Ok(quick_xml::events::Event::Start(ref tag) | quick_xml::events::Event::Empty(ref tag)) =>
             {
-                (
-                    "%Y/%m/%d", /* Year/month/day format. Same as %Y/%m/%d. */
-                    "Year/month/day (e.g., 2025/10/31)",
-                )
-            },
-            Self::DtFmt3 =>
-            {
-                (
-                    "%D", /* Month/day/year format. Same as %m/%d/%y. */
-                    "Month/day/year (e.g., 10/31/25)",
-                )
-            },
-            Self::DtFmt4 =>
-            {
-                (
-                    "%v", /* Day-month-year format. Same as %e-%b-%Y. */
-                    "Day-month-year (e.g., 31-Oct-2025)",
-                )
+                if is_layer_name(tag, name, &ns_keys)
+                {
+                    return Some(pos);
+                }
             },
-            _ => ("", "Custom"),
+            _ =>
+            {},
         }
     }
+
+    None
 }
 
-#[derive(
-    Debug,
-    PartialEq,
-    strum::EnumString,
-    strum::Display,
-    strum::EnumIter,
-    Copy,
-    Clone,
-    Serialize,
-    Deserialize,
-)]
-enum AppInfoFormatTime
+fn check_config_layer(src_buf: &[u8], section: &str, layer: &str, owner: Option<&str>) -> Option<String>
 {
-    TmFmt1,
-    TmFmt2,
-    TmFmt3,
-    TmFmt4,
-    TmFmt5,
-    TmCustom,
+    // a layer named in the config
+
+    if let Ok(None) = filter_xml_layer(src_buf, layer)
+    {
+        Some(match owner
+        {
+            Some(x) => format!("{} : missing layer `{}` of `{}`", section, layer, x),
+            None => format!("{} : missing layer `{}`", section, layer),
+        })
+    }
+    else
+    {
+        None
+    }
 }
 
-impl AppInfoFormatTime
+fn check_config_pivot(src_buf: &[u8], section: &str, layer: &str, pivot: &Option<String>) -> Option<String>
 {
-    fn format_str(&self) -> (&str, &str)
-    {
-        // see https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+    let pivot = pivot.as_ref()?;
 
-        match self
+    match filter_xml_layer(src_buf, pivot)
+    {
+        Ok(None) => Some(format!("{} : missing pivot layer `{}` of `{}`", section, pivot, layer)),
+        Ok(Some(src_pivot)) =>
         {
-            Self::TmFmt1 =>
-            {
-                (
-                    "%r", /* Locale’s 12 hour clock time. (e.g., 11:11:04 PM). Falls back to %X if the locale does not have a 12 hour clock format. */
-                    "12 hour clock time with AM/PM (e.g., 11:11:04 PM)",
-                )
-            },
-            Self::TmFmt2 =>
-            {
-                (
-                    "%I:%M:%S", /* Locale’s 12 hour clock time. (e.g., 11:11:04). Falls back to %X if the locale does not have a 12 hour clock format. */
-                    "12 hour clock time without AM/PM (e.g., 11:11:04)",
-                )
-            },
-            Self::TmFmt3 =>
+            if let Ok(None) = parse_xml_center_layer(&src_pivot, pivot)
             {
-                (
-                    "%T", /* Hour-minute-second format. Same as %H:%M:%S. */
-                    "Hour-minute-second (e.g., 23:11:04) ",
-                )
-            },
-            Self::TmFmt4 =>
+                let line = find_layer_line_name(src_buf, pivot).unwrap_or_default();
+
+                Some(format!(
+                    "{}: pivot layer `{}` of `{}` has no <circle> or <ellipse>",
+                    line, pivot, layer
+                ))
+            }
+            else
             {
-                (
-                    "%R", /* Hour-minute format. Same as %H:%M. */
-                    "Hour-minute (e.g., 23:11)",
-                )
-            },
-            Self::TmFmt5 => ("%p", "AM/PM (e.g., AM)"),
-            _ => ("", "Custom"),
-        }
+                None
+            }
+        },
+        _ => None,
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct AppInfo
+fn check_theme(path: &str) -> bool
 {
-    always_on_top: bool,
-    lock_pos: bool,
-    show_seconds: bool,
-    enable_sub_second_handle: bool,
-    enable_second_handle_smoothly: bool,
-    enable_text_time_zone: bool,
-    enable_text_date: bool,
-    enable_text_time: bool,
-    enable_text_time_segment: bool,
-    enable_text_time_segment_hour12: bool,
-    enable_text_time_segment_dotblink: bool,
-    text_format_date: AppInfoFormatDate,
-    text_format_date_custom: Option<String>,
-    text_format_time: AppInfoFormatTime,
-    text_format_time_custom: Option<String>,
-    time_zone: String,
-    theme: AppInfoTheme,
-    theme_custom: Option<String>,
-    zoom: u32,
-    window_pos: Option<(i32, i32)>,
-    #[serde(skip)]
-    zoom_update: bool,
-    #[serde(skip)]
-    time_disp: NaiveDateTime,
-    #[serde(skip)]
-    time_disp_st: Option<(NaiveDateTime, DateTime<Utc>)>,
-    #[serde(skip)]
-    timer_sourceid: RefCell<Option<gtk::glib::SourceId>>,
-    #[serde(skip)]
-    time_disp_force: Option<NaiveTime>,
-    #[serde(skip)]
-    theme_names: HashMap<AppInfoTheme, (Option<String>, Option<String>)>,
-}
+    // --check-theme <file.svg>
+    // runs the load_xml pipeline and reports the problems that load_xml ignores
 
-impl AppInfo
-{
-    fn new() -> Self
+    let mut errors = Vec::<String>::new();
+    let mut warnings = Vec::<String>::new();
+
+    let mut src_buf = Vec::<u8>::new();
+
+    if let Err(err) = File::open(path).and_then(|mut src| src.read_to_end(&mut src_buf))
     {
-        Self {
-            always_on_top: true,
-            lock_pos: false,
-            show_seconds: true,
-            enable_sub_second_handle: false,
-            enable_second_handle_smoothly: true,
-            enable_text_time_zone: true,
-            enable_text_date: true,
-            enable_text_time: true,
-            enable_text_time_segment: true,
-            enable_text_time_segment_hour12: false,
-            enable_text_time_segment_dotblink: true,
-            text_format_date: AppInfoFormatDate::DtFmt1,
-            text_format_date_custom: None,
-            text_format_time: AppInfoFormatTime::TmFmt1,
-            text_format_time_custom: None,
-            time_zone: String::new(),
-            theme: AppInfoTheme::Theme1,
-            theme_custom: None,
-            zoom: 100,
-            window_pos: None,
-            zoom_update: true,
-            time_disp: DateTime::UNIX_EPOCH.naive_utc(),
-            time_disp_st: None,
-            timer_sourceid: RefCell::new(None),
-            time_disp_force: None,
-            theme_names: HashMap::new(),
+        eprintln!("{}: error: {}", path, err);
+        return false;
+    }
+
+    // xml
+
+    {
+        let mut r_src = XmlInputReader::from_reader(&src_buf[..]);
+
+        loop
+        {
+            match r_src.read_event()
+            {
+                Ok(quick_xml::events::Event::Eof) => break,
+                Ok(_) =>
+                {},
+                Err(err) =>
+                {
+                    let (line, col) =
+                        byte_pos_to_line_col(&src_buf, r_src.error_position() as usize);
+                    errors.push(format!("{}:{}: xml: {}", line, col, err));
+                    break;
+                },
+            }
         }
     }
 
-    fn reset(&mut self)
+    if errors.is_empty()
     {
-        self.zoom_update = true;
-        self.time_disp = Local::now().date_naive().and_hms_opt(0, 0, 0).unwrap();
-        self.time_disp_st = None;
+        // layers
+
+        for target in [
+            LayerTarget::Base,
+            LayerTarget::LongHandle,
+            LayerTarget::ShortHandle,
+            LayerTarget::CenterCircle,
+        ]
+        {
+            if let Ok(None) = filter_xml(&src_buf, target)
+            {
+                errors.push(format!("missing required layer `{}`", target));
+            }
+        }
 
-        self.theme_custom = if let Ok(x) = std::env::var(ENV_KEY_THEME_CUSTOM)
+        if let Ok(Some(src_xml)) = filter_xml(&src_buf, LayerTarget::Base)
+            && let Ok((sz, _, _)) = parse_xml_sz_and_vbox(&src_xml)
+            && (sz.x <= 0 || sz.y <= 0)
+        {
+            errors.push(String::from("the size of the image is unknown. set width/height or viewBox to <svg>"));
+        }
+
+        for target in [
+            LayerTarget::CenterCircle,
+            LayerTarget::MoonPhaseCenterCircle,
+        ]
+        {
+            if let Ok(Some(src_xml)) = filter_xml(&src_buf, target)
+                && let Ok(None) = parse_xml_center(&src_xml, target)
+            {
+                let line = find_layer_line(&src_buf, target).unwrap_or_default();
+
+                errors.push(format!(
+                    "{}: layer `{}` has no <circle> or <ellipse>. the center of rotation is unknown",
+                    line, target
+                ));
+            }
+        }
+
+        for sub_dial in load_xml_config(&src_buf).get_sub_dials()
+        {
+            let handle = sub_dial.handle_layer();
+            let center_circle = sub_dial.center_circle_layer();
+
+            if let Ok(Some(_)) = filter_xml_layer(&src_buf, &handle)
+            {
+                match filter_xml_layer(&src_buf, &center_circle)
+                {
+                    Ok(None) =>
+                    {
+                        warnings.push(format!("layer `{}` without layer `{}`", handle, center_circle));
+                    },
+                    Ok(Some(src_xml)) =>
+                    {
+                        if let Ok(None) = parse_xml_center_layer(&src_xml, &center_circle)
+                        {
+                            let line = find_layer_line_name(&src_buf, &center_circle).unwrap_or_default();
+
+                            errors.push(format!(
+                                "{}: layer `{}` has no <circle> or <ellipse>. the center of rotation is unknown",
+                                line, center_circle
+                            ));
+                        }
+                    },
+                    _ =>
+                    {},
+                }
+            }
+        }
+
+        if let Ok(Some(_)) = filter_xml(&src_buf, LayerTarget::MoonPhaseDisc)
+            && let Ok(None) = filter_xml(&src_buf, LayerTarget::MoonPhaseAperture)
+        {
+            warnings.push(format!(
+                "layer `{}` without layer `{}`",
+                LayerTarget::MoonPhaseDisc,
+                LayerTarget::MoonPhaseAperture
+            ));
+        }
+
+        // config
+
+        if let Ok(Some(src_xml)) = filter_xml(&src_buf, LayerTarget::Config)
         {
-            Some(x)
+            match parse_xml_config_text(&src_xml)
+            {
+                Ok(text) =>
+                {
+                    if let Err(err) = toml::from_str::<ImageInfoConfig>(&text)
+                    {
+                        // the position in the text of the layer -> the position in the source
+
+                        let start = find_layer_pos_name(&src_buf, &LayerTarget::Config.to_string()).unwrap_or_default();
+
+                        let pos = parse_xml_config_text_pos(&src_buf[start ..])
+                            .ok()
+                            .zip(err.span())
+                            .and_then(|((_, text_pos), span)| {
+                                text_pos.get(span.start).or(text_pos.last()).copied()
+                            })
+                            .unwrap_or_default();
+
+                        let (line, col) = byte_pos_to_line_col(&src_buf, start + pos);
+
+                        errors.push(format!(
+                            "{}:{}: layer `{}` : {}",
+                            line,
+                            col,
+                            LayerTarget::Config,
+                            err.message().trim_end()
+                        ));
+                    }
+                },
+                Err(err) =>
+                {
+                    errors.push(format!("layer `{}` : {}", LayerTarget::Config, err));
+                },
+            }
         }
         else
         {
-            None
-        };
+            warnings.push(format!("missing layer `{}`", LayerTarget::Config));
+        }
 
-        self.time_disp_force = if let Ok(x) = std::env::var(ENV_KEY_FIX_TIME)
+        // hands
+
+        if let Ok(Some(src_xml)) = filter_xml(&src_buf, LayerTarget::Config)
+            && let Ok(text) = parse_xml_config_text(&src_xml)
+            && let Ok(config) = toml::from_str::<ImageInfoConfig>(&text)
         {
-            if let Ok(x) = NaiveTime::parse_from_str(&x, "%H:%M:%S")
+            for hand in config.hands.unwrap_or_default()
             {
-                Some(x)
+                errors.extend(check_config_layer(&src_buf, "hands", &hand.layer, None));
+                errors.extend(check_config_pivot(&src_buf, "hands", &hand.layer, &hand.pivot));
+
+                if let Some(points) = hand.range.points.as_ref()
+                    && points.windows(2).any(|w| w[0].0 > w[1].0)
+                {
+                    errors.push(format!("hands : `points` of `{}` are not in ascending order of value", hand.layer));
+                }
             }
-            else if let Ok(x) = NaiveTime::parse_from_str(&x, "%H:%M")
+
+            for wheel in config.wheels.unwrap_or_default()
             {
-                Some(x)
+                errors.extend(check_config_layer(&src_buf, "wheels", &wheel.layer, None));
+                errors.extend(check_config_pivot(&src_buf, "wheels", &wheel.layer, &wheel.pivot));
+
+                for x in [&wheel.window, &wheel.aperture].into_iter().flatten()
+                {
+                    errors.extend(check_config_layer(&src_buf, "wheels", x, Some(&wheel.layer)));
+                }
             }
-            else
+
+            for sub_dial in config.sub_dials.unwrap_or_default()
             {
-                None
+                errors.extend(check_config_layer(&src_buf, "sub_dials", &sub_dial.handle_layer(), Some(&sub_dial.name)));
+
+                for x in [&sub_dial.base, &sub_dial.center_circle].into_iter().flatten()
+                {
+                    errors.extend(check_config_layer(&src_buf, "sub_dials", x, Some(&sub_dial.name)));
+                }
+
+                if let Some(points) = sub_dial.range.points.as_ref()
+                    && points.windows(2).any(|w| w[0].0 > w[1].0)
+                {
+                    errors.push(format!("sub_dials : `points` of `{}` are not in ascending order of value", sub_dial.name));
+                }
+            }
+
+            if let Some(x) = config.daylight_arc
+                && !matches!(x, TimeDriver::Hour12 | TimeDriver::Hour24)
+            {
+                errors.push(String::from("daylight_arc : must be \"hour24\" or \"hour12\""));
+            }
+
+            for (key, color) in [
+                ("daylight_arc_color", &config.daylight_arc_color),
+                ("countdown_arc_color", &config.countdown_arc_color),
+            ]
+            {
+                if let Some(x) = color.as_ref()
+                    && parse_color(x).is_none()
+                {
+                    errors.push(format!("{} : invalid color `{}`. use \"#rrggbb\" or \"#rrggbbaa\"", key, x));
+                }
             }
         }
-        else
+
+        // base_text
+
+        if let Ok(Some(src_xml)) = filter_xml(&src_buf, LayerTarget::BaseText)
+            && let Err(err) = svgclock_rs::minitemplate::parse(Cursor::new(&src_xml))
         {
-            None
-        };
+            // line and col of the filtered xml -> line and col of the source
+            // col: 0 based in the filtered xml, 1 based in the source ( the same as the xml errors )
 
-        // theme loading
+            static RE_LINE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"Line:(\d+)(?: Col:(\d+))?").unwrap());
+
+            let offset = find_layer_line(&src_buf, LayerTarget::BaseText).unwrap_or(1) as i64
+                - find_layer_line(&src_xml, LayerTarget::BaseText).unwrap_or(1) as i64;
+
+            let fn_line = |buf: &[u8], line: i64| -> Option<Vec<u8>> {
+                buf.split(|&x| x == b'\n').nth(usize::try_from(line - 1).ok()?).map(|x| x.to_vec())
+            };
+
+            let message = err.to_string();
+            let message = RE_LINE.replace_all(&message, |caps: &regex::Captures| {
+                let line = i64::from_str(&caps[1]).unwrap_or_default();
+
+                match caps.get(2)
+                {
+                    Some(col) =>
+                    {
+                        let col = usize::from_str(col.as_str()).unwrap_or_default();
+
+                        // the line of the filtered xml may start in the middle of the line of the source
+                        let shift = fn_line(&src_xml, line)
+                            .zip(fn_line(&src_buf, line + offset))
+                            .and_then(|(xml_line, src_line)| {
+                                if xml_line.is_empty()
+                                {
+                                    Some(0)
+                                }
+                                else
+                                {
+                                    src_line.windows(xml_line.len()).position(|x| x == xml_line)
+                                }
+                            })
+                            .unwrap_or_default();
 
-        for theme in AppInfoTheme::iter()
+                        format!("Line:{} Col:{}", line + offset, shift + col + 1)
+                    },
+                    None => format!("Line:{}", line + offset),
+                }
+            });
+
+            errors.push(format!("layer `{}` : {}", LayerTarget::BaseText, message));
+        }
+
+        // svg
+
+        if let Err(err) = load_xml(&src_buf)
+        {
+            errors.push(err.to_string());
+        }
+    }
+
+    for x in &warnings
+    {
+        eprintln!("{}: warning: {}", path, x);
+    }
+
+    for x in &errors
+    {
+        eprintln!("{}: error: {}", path, x);
+    }
+
+    if errors.is_empty()
+    {
+        println!("{}: ok", path);
+    }
+
+    errors.is_empty()
+}
+
+fn load_logo() -> Option<Pixbuf>
+{
+    if ENABLE_FILE_INCLUDE
+    {
+        let mut src_buf = &INCLUDE_BYTES_LOGO_PNG[..];
+        let surface = ImageSurface::create_from_png(&mut src_buf).unwrap();
+        return gdk::pixbuf_get_from_surface(&surface, 0, 0, surface.width(), surface.height());
+    }
+    else
+    {
+        // load logo
+        let mut src_buf = Vec::<u8>::new();
+
+        let mut src = File::open(FILE_LOGO_PNG).unwrap();
+        src.read_to_end(&mut src_buf).unwrap();
+
+        if FILE_LOGO_PNG.ends_with(".svg")
         {
-            if let Some(image_info) = load_theme(theme, self.theme_custom.clone())
+            if let Ok(result) = parse_xml_sz_and_vbox(&src_buf)
             {
-                self.theme_names.insert(
-                    theme,
-                    (
-                        image_info.config.get_theme_name(),
-                        image_info.config.get_theme_description(),
-                    ),
+                let sz = result.0;
+                let surface = ImageSurface::create(Format::ARgb32, sz.x, sz.y).unwrap();
+
+                {
+                    let svg_stream =
+                        gtk::gio::MemoryInputStream::from_bytes(&gtk::glib::Bytes::from(&src_buf));
+
+                    let svg_handle = rsvg::Loader::new()
+                        .read_stream(
+                            &svg_stream,
+                            None::<&gtk::gio::File>,
+                            None::<&gtk::gio::Cancellable>,
+                        )
+                        .unwrap();
+
+                    let cctx = Context::new(&surface).unwrap();
+                    let viewport = Rectangle::new(0.0, 0.0, sz.x as f64, sz.y as f64);
+
+                    let svg_renderer = rsvg::CairoRenderer::new(&svg_handle);
+                    svg_renderer.render_document(&cctx, &viewport).unwrap();
+                }
+
+                return gdk::pixbuf_get_from_surface(
+                    &surface,
+                    0,
+                    0,
+                    surface.width(),
+                    surface.height(),
                 );
             }
         }
+        else if FILE_LOGO_PNG.ends_with(".png")
+        {
+            let mut src_buf = &src_buf[..]; // with io.Read Trait
+
+            let surface = ImageSurface::create_from_png(&mut src_buf).unwrap();
+
+            return gdk::pixbuf_get_from_surface(&surface, 0, 0, surface.width(), surface.height());
+        }
+    }
+
+    None
+}
+
+#[derive(
+    Debug,
+    PartialEq,
+    strum::EnumString,
+    strum::Display,
+    strum::EnumIter,
+    Copy,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+enum AppInfoFormatDate
+{
+    DtFmt1,
+    DtFmt2,
+    DtFmt3,
+    DtFmt4,
+    DtCustom,
+}
+
+impl AppInfoFormatDate
+{
+    fn format_str(&self) -> (&str, &str)
+    {
+        // see https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+
+        match self
+        {
+            Self::DtFmt1 =>
+            {
+                (
+                    "%F", /* Year-month-day format (ISO 8601). Same as %Y-%m-%d. */
+                    "Year-month-day (e.g., 2025-10-31)",
+                )
+            },
+            Self::DtFmt2 =>
+            {
+                (
+                    "%Y/%m/%d", /* Year/month/day format. Same as %Y/%m/%d. */
+                    "Year/month/day (e.g., 2025/10/31)",
+                )
+            },
+            Self::DtFmt3 =>
+            {
+                (
+                    "%D", /* Month/day/year format. Same as %m/%d/%y. */
+                    "Month/day/year (e.g., 10/31/25)",
+                )
+            },
+            Self::DtFmt4 =>
+            {
+                (
+                    "%v", /* Day-month-year format. Same as %e-%b-%Y. */
+                    "Day-month-year (e.g., 31-Oct-2025)",
+                )
+            },
+            _ => ("", "Custom"),
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    PartialEq,
+    strum::EnumString,
+    strum::Display,
+    strum::EnumIter,
+    Copy,
+    Clone,
+    Serialize,
+    Deserialize,
+)]
+enum AppInfoFormatTime
+{
+    TmFmt1,
+    TmFmt2,
+    TmFmt3,
+    TmFmt4,
+    TmFmt5,
+    TmCustom,
+}
+
+impl AppInfoFormatTime
+{
+    fn format_str(&self) -> (&str, &str)
+    {
+        // see https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+
+        match self
+        {
+            Self::TmFmt1 =>
+            {
+                (
+                    "%r", /* Locale’s 12 hour clock time. (e.g., 11:11:04 PM). Falls back to %X if the locale does not have a 12 hour clock format. */
+                    "12 hour clock time with AM/PM (e.g., 11:11:04 PM)",
+                )
+            },
+            Self::TmFmt2 =>
+            {
+                (
+                    "%I:%M:%S", /* Locale’s 12 hour clock time. (e.g., 11:11:04). Falls back to %X if the locale does not have a 12 hour clock format. */
+                    "12 hour clock time without AM/PM (e.g., 11:11:04)",
+                )
+            },
+            Self::TmFmt3 =>
+            {
+                (
+                    "%T", /* Hour-minute-second format. Same as %H:%M:%S. */
+                    "Hour-minute-second (e.g., 23:11:04) ",
+                )
+            },
+            Self::TmFmt4 =>
+            {
+                (
+                    "%R", /* Hour-minute format. Same as %H:%M. */
+                    "Hour-minute (e.g., 23:11)",
+                )
+            },
+            Self::TmFmt5 => ("%p", "AM/PM (e.g., AM)"),
+            _ => ("", "Custom"),
+        }
+    }
+}
+
+/*
+    A clock window. Each has its own time zone, theme, zoom and position.
+
+    The first clock is saved at the top level of the configuration file ( as the single clock of
+    the older versions ), the others in [[clocks]].
+*/
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct ClockInfo
+{
+    time_zone: String,
+    time_zone2: String, // the second time zone. gmt_handle, time2, ...
+    theme: String,
+    zoom: u32,
+    window_pos: Option<(i32, i32)>,
+    #[serde(skip)]
+    id: u32,
+    #[serde(skip)]
+    zoom_update: bool,
+    #[serde(skip)]
+    time_disp: NaiveDateTime,
+    #[serde(skip)]
+    time_disp_st: Option<(NaiveDateTime, DateTime<Utc>)>,
+    #[serde(skip)]
+    time_disp_offset: TimeDelta,
+    #[serde(skip)]
+    time_disp2_offset: TimeDelta,
+    #[serde(skip)]
+    digit_time: NaiveDateTime, // time_disp in seconds. for the digit transition
+    #[serde(skip)]
+    digit_transition: Option<(NaiveDateTime, Instant)>, // the outgoing time, the start
+    #[serde(skip)]
+    timer_sourceid: RefCell<Option<gtk::glib::SourceId>>,
+    #[serde(skip)]
+    theme_monitor: Option<gtk::gio::FileMonitor>,
+    #[serde(skip)]
+    theme_error: Option<String>,
+}
+
+impl Default for ClockInfo
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl ClockInfo
+{
+    fn new() -> Self
+    {
+        Self {
+            time_zone: String::new(),
+            time_zone2: String::from("UTC"),
+            theme: String::from(THEME_ID_DEFAULT),
+            zoom: 100,
+            window_pos: None,
+            id: 0,
+            zoom_update: true,
+            time_disp: DateTime::UNIX_EPOCH.naive_utc(),
+            time_disp_st: None,
+            time_disp_offset: TimeDelta::zero(),
+            time_disp2_offset: TimeDelta::zero(),
+            digit_time: DateTime::UNIX_EPOCH.naive_utc(),
+            digit_transition: None,
+            timer_sourceid: RefCell::new(None),
+            theme_monitor: None,
+            theme_error: None,
+        }
+    }
+
+    fn reset(&mut self, id: u32)
+    {
+        self.id = id;
+        self.zoom_update = true;
+        self.time_disp = Local::now().date_naive().and_hms_opt(0, 0, 0).unwrap();
+        self.time_disp_st = None;
+        self.digit_time = self.time_disp;
+        self.digit_transition = None;
+    }
+
+    fn time_zone_mut(&mut self, second: bool) -> &mut String
+    {
+        if second { &mut self.time_zone2 } else { &mut self.time_zone }
+    }
+
+    // the time of the second time zone. moves with time_disp
+    fn time_disp2(&self) -> NaiveDateTime
+    {
+        self.time_disp - self.time_disp_offset + self.time_disp2_offset
+    }
+
+    // the outgoing time and the progress ( 0.0 .. 1.0, eased ) of the digit transition
+    fn digit_transition(&self) -> Option<(NaiveDateTime, f64)>
+    {
+        let (time_from, time_st) = self.digit_transition?;
+
+        let mut tweener = tween::Tweener::quad_in_out(0.0, 1.0, DIGIT_TRANSITION_MILLIS);
+
+        Some((time_from, tweener.move_to(time_st.elapsed().as_millis() as i64)))
+    }
+
+    // stop the timer and the monitor of the theme of the closed window
+    fn close(&mut self)
+    {
+        if let Some(sourceid) = self.timer_sourceid.take()
+        {
+            sourceid.remove();
+        }
+
+        if let Some(monitor) = self.theme_monitor.take()
+        {
+            monitor.cancel();
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AppInfo
+{
+    always_on_top: bool,
+    lock_pos: bool,
+    show_seconds: bool,
+    enable_sub_second_handle: bool,
+    enable_second_handle_smoothly: bool,
+    enable_text_time_zone: bool,
+    enable_text_date: bool,
+    enable_text_time: bool,
+    enable_text_time_segment: bool,
+    enable_text_time_segment_hour12: bool,
+    enable_text_time_segment_dotblink: bool,
+    text_format_date: AppInfoFormatDate,
+    text_format_date_custom: Option<String>,
+    text_format_time: AppInfoFormatTime,
+    text_format_time_custom: Option<String>,
+    #[serde(default)]
+    theme_customs: Vec<String>,
+    #[serde(default)]
+    theme_dirs: Vec<String>,
+    #[serde(default)]
+    latitude: Option<f64>,
+    #[serde(default)]
+    longitude: Option<f64>,
+    #[serde(default = "default_true")]
+    enable_daylight_arc: bool,
+    #[serde(default)]
+    countdown: Countdown,
+    #[serde(default)]
+    alarms: Vec<Alarm>,
+    #[serde(default)]
+    chime: ChimeConfig,
+    #[serde(flatten)]
+    clock: ClockInfo, // the first clock. at the top level as the single clock of the older versions
+    #[serde(default)]
+    clocks: Vec<ClockInfo>, // the other clocks
+    #[serde(skip)]
+    clock_id_next: u32,
+    #[serde(skip)]
+    time_disp_force: Option<NaiveTime>,
+    #[serde(skip)]
+    theme_custom: Option<String>,
+    #[serde(skip)]
+    themes: LinkedHashMap<String, ThemeEntry>,
+    #[serde(skip)]
+    stopwatch: Stopwatch,
+    #[serde(skip)]
+    countdown_flash: Option<Instant>,
+    #[serde(skip)]
+    alarm_checked: Option<NaiveDateTime>,
+    #[serde(skip)]
+    alarm_snoozes: Vec<(u32, NaiveDateTime)>, // ( the id of the alarm, the time to fire )
+    #[serde(skip)]
+    alarm_id_next: u32,
+    #[serde(skip)]
+    chime_checked: Option<NaiveDateTime>,
+}
+
+impl AppInfo
+{
+    fn new() -> Self
+    {
+        Self {
+            always_on_top: true,
+            lock_pos: false,
+            show_seconds: true,
+            enable_sub_second_handle: false,
+            enable_second_handle_smoothly: true,
+            enable_text_time_zone: true,
+            enable_text_date: true,
+            enable_text_time: true,
+            enable_text_time_segment: true,
+            enable_text_time_segment_hour12: false,
+            enable_text_time_segment_dotblink: true,
+            text_format_date: AppInfoFormatDate::DtFmt1,
+            text_format_date_custom: None,
+            text_format_time: AppInfoFormatTime::TmFmt1,
+            text_format_time_custom: None,
+            theme_customs: Vec::new(),
+            theme_dirs: Vec::new(),
+            latitude: None,
+            longitude: None,
+            enable_daylight_arc: true,
+            countdown: Countdown::new(),
+            alarms: Vec::new(),
+            chime: ChimeConfig::new(),
+            clock: ClockInfo::new(),
+            clocks: Vec::new(),
+            clock_id_next: 0,
+            time_disp_force: None,
+            theme_custom: None,
+            themes: LinkedHashMap::new(),
+            stopwatch: Stopwatch::new(),
+            countdown_flash: None,
+            alarm_checked: None,
+            alarm_snoozes: Vec::new(),
+            alarm_id_next: 0,
+            chime_checked: None,
+        }
+    }
+
+    fn reset(&mut self)
+    {
+        self.theme_custom = if let Ok(x) = std::env::var(ENV_KEY_THEME_CUSTOM)
+        {
+            Some(canonicalize_theme_path(&x))
+        }
+        else
+        {
+            None
+        };
+
+        self.time_disp_force = if let Ok(x) = std::env::var(ENV_KEY_FIX_TIME)
+        {
+            if let Ok(x) = NaiveTime::parse_from_str(&x, "%H:%M:%S")
+            {
+                Some(x)
+            }
+            else if let Ok(x) = NaiveTime::parse_from_str(&x, "%H:%M")
+            {
+                Some(x)
+            }
+            else
+            {
+                None
+            }
+        }
+        else
+        {
+            None
+        };
+
+        // theme loading
+
+        self.themes = scan_themes(&self.theme_dirs, &self.theme_customs);
+
+        // the theme of THEME_CUSTOM is not saved in theme_customs
+        if let Some(x) = self.theme_custom.clone()
+        {
+            self.insert_theme_custom(&x);
+        }
+
+        debug!("themes: {:?}", self.themes);
+
+        // alarms of the older versions have no id
+
+        self.alarm_id_next = assign_alarm_ids(&mut self.alarms, 1);
+
+        // clocks
+
+        self.clock_id_next = 0;
+
+        for i in 0 ..= self.clocks.len()
+        {
+            let id = self.clock_id_next;
+            self.clock_id_next += 1;
+
+            let clock = if i == 0 { &mut self.clock } else { &mut self.clocks[i - 1] };
+
+            clock.reset(id);
+
+            // a theme of the id "Theme1" is kept
+            if !self.themes.contains_key(&clock.theme)
+                && let Some(x) = theme_id_from_legacy(&clock.theme, &self.theme_custom)
+            {
+                clock.theme = x;
+            }
+
+            if !self.themes.contains_key(&clock.theme)
+            {
+                clock.theme = String::from(THEME_ID_DEFAULT);
+            }
+        }
+    }
+
+    fn load_theme(&self, theme: &str) -> Result<ImageInfo, Box<dyn Error>>
+    {
+        if let Some(entry) = self.themes.get(theme)
+        {
+            load_theme(entry)
+        }
+        else
+        {
+            Err(format!("theme not found : {}", theme).into())
+        }
+    }
+
+    fn clock_ids(&self) -> Vec<u32>
+    {
+        std::iter::once(&self.clock)
+            .chain(self.clocks.iter())
+            .map(|x| x.id)
+            .collect()
+    }
+
+    fn clock(&self, id: u32) -> Option<&ClockInfo>
+    {
+        std::iter::once(&self.clock)
+            .chain(self.clocks.iter())
+            .find(|x| x.id == id)
+    }
+
+    fn clock_mut(&mut self, id: u32) -> Option<&mut ClockInfo>
+    {
+        std::iter::once(&mut self.clock)
+            .chain(self.clocks.iter_mut())
+            .find(|x| x.id == id)
+    }
+
+    // the time of the first clock for the alarms and the chimes. not by the drawing ( time_disp_offset )
+    // FIX_TIME: the fixed time. nothing fires
+    fn time_events(&self) -> NaiveDateTime
+    {
+        let time_now = Local::now();
+
+        if let Some(x) = self.time_disp_force
+        {
+            time_now.with_time(x).unwrap().naive_local()
+        }
+        else
+        {
+            time_in_zone(&time_now, &self.clock.time_zone)
+        }
+    }
+
+    // a new clock with the settings of the clock `src_id`, beside it
+    fn add_clock(&mut self, src_id: u32, window_pos: Option<(i32, i32)>) -> u32
+    {
+        let id = self.clock_id_next;
+        self.clock_id_next += 1;
+
+        let mut clock = ClockInfo::new();
+
+        if let Some(src) = self.clock(src_id)
+        {
+            clock.time_zone = src.time_zone.clone();
+            clock.time_zone2 = src.time_zone2.clone();
+            clock.theme = src.theme.clone();
+            clock.zoom = src.zoom;
+        }
+
+        clock.window_pos = window_pos;
+        clock.reset(id);
+
+        self.clocks.push(clock);
+
+        id
+    }
+
+    // the last clock is not removed
+    fn remove_clock(&mut self, id: u32) -> Option<ClockInfo>
+    {
+        if self.clocks.is_empty()
+        {
+            None
+        }
+        else if self.clock.id == id
+        {
+            Some(std::mem::replace(&mut self.clock, self.clocks.remove(0)))
+        }
+        else
+        {
+            let index = self.clocks.iter().position(|x| x.id == id)?;
+
+            Some(self.clocks.remove(index))
+        }
+    }
+
+    fn add_theme_custom(&mut self, path: &str) -> String
+    {
+        let path = canonicalize_theme_path(path);
+
+        if !self.theme_customs.contains(&path)
+        {
+            self.theme_customs.push(path.clone());
+        }
+
+        self.insert_theme_custom(&path)
+    }
+
+    // to the themes only. path = canonicalized
+    fn insert_theme_custom(&mut self, path: &str) -> String
+    {
+        let id = theme_id_custom(path);
+
+        let mut entry = ThemeEntry::new(ThemeSource::File(PathBuf::from(path)));
+        entry.load_names();
+
+        if let Some(x) = self.themes.get_mut(&id)
+        {
+            *x = entry;
+        }
+        else
+        {
+            self.themes.insert(id.clone(), entry);
+        }
+
+        id
+    }
+
+    fn remove_theme_custom(&mut self, path: &str)
+    {
+        self.theme_customs.retain(|x| x != path);
+        self.themes.remove(&theme_id_custom(path));
+    }
+
+    // {{date}}, {{date2}}. None for a broken custom format
+    fn format_text_date(&self, dt: &NaiveDateTime) -> Option<String>
+    {
+        let format = match (&self.text_format_date, &self.text_format_date_custom)
+        {
+            (AppInfoFormatDate::DtCustom, Some(x)) => x.as_str(),
+            (x, _) => x.format_str().0,
+        };
+
+        let mut buffer = String::new();
+
+        dt.format(format).write_to(&mut buffer).ok().map(|_| buffer)
+    }
+
+    // {{time}}, {{time2}}. None for a broken custom format
+    fn format_text_time(&self, dt: &NaiveDateTime) -> Option<String>
+    {
+        let format = match (&self.text_format_time, &self.text_format_time_custom)
+        {
+            (AppInfoFormatTime::TmCustom, Some(x)) => x.as_str(),
+            (x, _) => x.format_str().0,
+        };
+
+        let mut buffer = String::new();
+
+        dt.format(format).write_to(&mut buffer).ok().map(|_| buffer)
+    }
+
+    fn location(&self) -> Option<(f64, f64)>
+    {
+        if let Some(lat) = self.latitude
+            && let Some(lon) = self.longitude
+            && (-90.0 ..= 90.0).contains(&lat)
+            && (-180.0 ..= 180.0).contains(&lon)
+        {
+            Some((lat, lon))
+        }
+        else
+        {
+            None
+        }
+    }
+}
+
+fn default_true() -> bool
+{
+    true
+}
+
+fn canonicalize_theme_path(path: &str) -> String
+{
+    match std::fs::canonicalize(path)
+    {
+        Ok(x) => x.to_string_lossy().to_string(),
+        _ => String::from(path),
+    }
+}
+
+fn theme_id_from_legacy(theme: &str, theme_custom: &Option<String>) -> Option<String>
+{
+    // ver 0.4.0 and before saved the theme as "Theme1" .. "Theme8" or "Custom"
+
+    if theme == "Custom"
+    {
+        theme_custom.as_ref().map(|x| theme_id_custom(x))
+    }
+    else if let Some(x) = theme.strip_prefix("Theme")
+        && let Ok(x) = u32::from_str(x)
+    {
+        Some(format!("clock_theme_{}", x))
+    }
+    else
+    {
+        None
+    }
+}
+
+const MOVE_FAST_SECS: i64 = 5;
+
+// the local time in the time zone. "" = the local time zone of the system
+fn time_in_zone(time_now: &DateTime<Local>, time_zone: &str) -> NaiveDateTime
+{
+    if time_zone == ""
+    {
+        time_now.naive_local()
+    }
+    else if time_zone.starts_with("GMT+") || time_zone.starts_with("GMT-")
+    {
+        // FIX
+        // chrono::Tz::Etc__GMTMinus1 = +1 -> -1
+        // chrono::Tz::Etc__GMTPlus1  = -1 -> +1
+        // chrono::Tz::Etc__GMTMinus<x> = +<x> -> -<x>
+        // chrono::Tz::Etc__GMTPlus<x>  = -<x> -> +<x>
+        // see https://github.com/chronotope/chrono-tz/issues/16
+        // see https://github.com/eggert/tz/blob/ab21ad9710b88f28995b7ed47c6efda47ffb1be5/etcetera#L37-L43
+        // see ```
+        // # Be consistent with POSIX TZ settings in the Zone names,
+        // # even though this is the opposite of what many people expect.
+        // # POSIX has positive signs west of Greenwich, but many people expect
+        // # positive signs east of Greenwich.  For example, TZ='Etc/GMT+4' uses
+        // # the abbreviation "-04" and corresponds to 4 hours behind UT
+        // # (i.e. west of Greenwich) even though many people would expect it to
+        // # mean 4 hours ahead of UT (i.e. east of Greenwich).
+        // ```
+
+        if let Ok(time_delta) = i32::from_str(time_zone.trim_start_matches("GMT"))
+        {
+            let offset = chrono::FixedOffset::east_opt(time_delta * 60 * 60).unwrap();
+            time_now.with_timezone(&offset).naive_local()
+        }
+        else
+        {
+            time_now.naive_local()
+        }
+    }
+    else
+    {
+        let tz: Result<chrono_tz::Tz, _> = time_zone.parse();
+
+        match tz
+        {
+            Ok(offset) => time_now.with_timezone(&offset).naive_local(),
+            _ => time_now.naive_local(),
+        }
+    }
+}
+
+// transition_positions = the digit positions of the digit transition of the theme. see ImageInfo
+fn update_watch(da: &DrawingArea, app_info: &mut AppInfo, clock_id: u32, transition_positions: &[&str])
+{
+    let Some((time_zone, time_zone2)) = app_info
+        .clock(clock_id)
+        .map(|x| (x.time_zone.clone(), x.time_zone2.clone()))
+    else
+    {
+        return;
+    };
+
+    let time_now = Local::now();
+
+    let time_now_naive = if app_info.time_disp_force.is_some()
+    {
+        time_now
+            .with_time(app_info.time_disp_force.as_ref().copied().unwrap())
+            .unwrap()
+            .naive_local()
+    }
+    else
+    {
+        time_in_zone(&time_now, &time_zone)
+    };
+
+    // time_disp - time_disp_offset = UTC
+
+    let time_disp_offset = if app_info.time_disp_force.is_some()
+    {
+        TimeDelta::seconds(time_now.offset().local_minus_utc() as i64)
+    }
+    else
+    {
+        time_now_naive - time_now.naive_utc()
+    };
+
+    let hour12 = app_info.enable_text_time_segment_hour12;
+
+    let Some(clock) = app_info.clock_mut(clock_id)
+    else
+    {
+        return;
+    };
+
+    clock.time_disp_offset = time_disp_offset;
+    clock.time_disp2_offset = time_in_zone(&time_now, &time_zone2) - time_now.naive_utc();
+
+    let is_fast_old = clock.time_disp_st.is_some() || clock.digit_transition.is_some();
+
+    let time_delta = (time_now_naive - clock.time_disp).num_seconds();
+
+    if time_delta.abs() <= 10
+    {
+        clock.time_disp = time_now_naive;
+        clock.time_disp_st = None;
+    }
+    else
+    {
+        if clock.time_disp_st.is_none()
+        {
+            clock.time_disp_st = Some((clock.time_disp, time_now.to_utc()));
+        }
+
+        let (time_disp_st, time_st) = clock.time_disp_st.unwrap();
+
+        let timestamp_disp_du = TimeDelta::seconds(MOVE_FAST_SECS);
+
+        let mut tweener =
+            tween::Tweener::quad_in_out(0.0, 1.0, timestamp_disp_du.num_milliseconds());
+
+        let timestamp_disp_diff = time_now.to_utc() - time_st;
+
+        let pos = tweener.move_to(timestamp_disp_diff.num_milliseconds());
+
+        if pos < 0.0 || pos >= 1.0
+        {
+            clock.time_disp = time_now_naive;
+            clock.time_disp_st = None;
+        }
+        else
+        {
+            let add = TimeDelta::milliseconds(
+                ((time_now_naive - time_disp_st).num_milliseconds() as f64 * pos) as i64,
+            );
+            clock.time_disp = time_disp_st + add;
+        }
+    }
+
+    // digit transition. when a digit in the theme changes. not while moving to the new time
+
+    let digit_time = clock.time_disp.with_nanosecond(0).unwrap_or(clock.time_disp);
+
+    if clock.time_disp_st.is_none()
+        && digit_time != clock.digit_time
+        && transition_positions
+            .iter()
+            .any(|x| digit(&clock.digit_time, x, hour12) != digit(&digit_time, x, hour12))
+    {
+        clock.digit_transition = Some((clock.digit_time, Instant::now()));
+    }
+
+    clock.digit_time = digit_time;
+
+    if transition_positions.is_empty()
+        || clock
+            .digit_transition
+            .is_some_and(|(_, time_st)| time_st.elapsed().as_millis() >= DIGIT_TRANSITION_MILLIS as u128)
+    {
+        clock.digit_transition = None;
+    }
+
+    let is_fast = clock.time_disp_st.is_some() || clock.digit_transition.is_some();
+
+    if is_fast_old != is_fast
+    {
+        // update timer
+
+        {
+            let da = da.clone();
+
+            let old_timer_sourceid =
+                clock
+                    .timer_sourceid
+                    .replace(Some(gtk::glib::source::timeout_add_local(
+                        std::time::Duration::from_millis(get_timer_interval(is_fast)),
+                        move || {
+                            da.queue_draw();
+                            gtk::glib::ControlFlow::Continue
+                        },
+                    )));
+
+            if let Some(sourceid) = old_timer_sourceid
+            {
+                sourceid.remove();
+            }
+        }
+    }
+}
+
+fn update_countdown(app: &Application, app_info: &mut AppInfo)
+{
+    if app_info.countdown.check_expired(&Utc::now())
+    {
+        let duration = format_remaining(app_info.countdown.duration());
+
+        info!("countdown {} expired", duration);
+
+        app_info.countdown_flash = Some(Instant::now());
+
+        let notification = gtk::gio::Notification::new(GTK_APPLICATION_TITLE);
+
+        notification.set_body(Some(&format!("Timer {} expired", duration)));
+        notification.set_priority(gtk::gio::NotificationPriority::High);
+
+        app.send_notification(Some("countdown"), &notification);
+    }
+
+    if app_info
+        .countdown_flash
+        .is_some_and(|x| x.elapsed().as_secs_f64() >= COUNTDOWN_FLASH_SECS)
+    {
+        app_info.countdown_flash = None;
+    }
+}
+
+fn alarm_notification_id(id: u32) -> String
+{
+    format!("alarm-{}", id)
+}
+
+fn update_alarms(app: &Application, app_info: &mut AppInfo)
+{
+    // the time of the first clock without the move to the new time. alarms are in the time zone of the first clock
+    let now = app_info.time_events();
+
+    let Some(from) = app_info.alarm_checked.replace(now)
+    else
+    {
+        return;
+    };
+
+    if now <= from || now - from > TimeDelta::minutes(ALARM_CATCH_UP_MINUTES)
+    {
+        return;
+    }
+
+    let fired = fire_alarms(&app_info.alarms, &mut app_info.alarm_snoozes, &from, &now);
+
+    for id in fired
+    {
+        let Some(alarm) = alarm_by_id(&app_info.alarms, id)
+        else
+        {
+            continue;
+        };
+
+        info!("alarm {} {} {}", id, alarm.time, alarm.label);
+
+        let notification = gtk::gio::Notification::new(if alarm.label.is_empty()
+        {
+            "Alarm"
+        }
+        else
+        {
+            &alarm.label
+        });
+
+        notification.set_body(Some(&alarm.time.format("%H:%M").to_string()));
+        notification.set_priority(gtk::gio::NotificationPriority::Urgent);
+        notification.add_button_with_target_value(
+            "Snooze",
+            &format!("app.{}", ACTION_ALARM_SNOOZE),
+            Some(&id.to_variant()),
+        );
+        notification.add_button_with_target_value(
+            "Dismiss",
+            &format!("app.{}", ACTION_ALARM_DISMISS),
+            Some(&id.to_variant()),
+        );
 
-        debug!("theme_names: {:?}", self.theme_names);
+        app.send_notification(Some(&alarm_notification_id(id)), &notification);
     }
 }
 
-const MOVE_FAST_SECS: i64 = 5;
+fn start_event_timer(app: &Application, app_info: &Rc<RefCell<AppInfo>>)
+{
+    // alarms, countdown, chimes. also while the windows are minimized or hidden
+
+    let app = app.clone();
+    let app_info = app_info.clone();
+
+    gtk::glib::source::timeout_add_local(std::time::Duration::from_millis(UPDATE_CYCLE_EVENTS), move || {
+        update_countdown(&app, &mut app_info.borrow_mut());
+        update_alarms(&app, &mut app_info.borrow_mut());
+        update_chimes(&mut app_info.borrow_mut());
+        gtk::glib::ControlFlow::Continue
+    });
+}
 
-fn update_watch(da: &DrawingArea, app_info: &mut AppInfo)
+fn add_alarm_actions(app: &Application, app_info: &Rc<RefCell<AppInfo>>)
 {
-    let time_now = Local::now();
+    // the buttons of the notification of the alarm. the parameter is the id of the alarm
 
-    let time_now_naive = if app_info.time_disp_force.is_some()
-    {
-        time_now
-            .with_time(app_info.time_disp_force.as_ref().copied().unwrap())
-            .unwrap()
-            .naive_local()
-    }
-    else if app_info.time_zone == ""
-    {
-        time_now.naive_local()
-    }
-    else
-    {
-        let time_zone = app_info.time_zone.clone();
+    let action_snooze = gtk::gio::SimpleAction::new(ACTION_ALARM_SNOOZE, Some(gtk::glib::VariantTy::UINT32));
 
-        if time_zone.starts_with("GMT+") || time_zone.starts_with("GMT-")
-        {
-            // FIX
-            // chrono::Tz::Etc__GMTMinus1 = +1 -> -1
-            // chrono::Tz::Etc__GMTPlus1  = -1 -> +1
-            // chrono::Tz::Etc__GMTMinus<x> = +<x> -> -<x>
-            // chrono::Tz::Etc__GMTPlus<x>  = -<x> -> +<x>
-            // see https://github.com/chronotope/chrono-tz/issues/16
-            // see https://github.com/eggert/tz/blob/ab21ad9710b88f28995b7ed47c6efda47ffb1be5/etcetera#L37-L43
-            // see ```
-            // # Be consistent with POSIX TZ settings in the Zone names,
-            // # even though this is the opposite of what many people expect.
-            // # POSIX has positive signs west of Greenwich, but many people expect
-            // # positive signs east of Greenwich.  For example, TZ='Etc/GMT+4' uses
-            // # the abbreviation "-04" and corresponds to 4 hours behind UT
-            // # (i.e. west of Greenwich) even though many people would expect it to
-            // # mean 4 hours ahead of UT (i.e. east of Greenwich).
-            // ```
+    {
+        let app = app.clone();
+        let app_info = app_info.clone();
 
-            if let Ok(time_delta) = i32::from_str(time_zone.trim_start_matches("GMT"))
-            {
-                let offset = chrono::FixedOffset::east_opt(time_delta * 60 * 60).unwrap();
-                time_now.with_timezone(&offset).naive_local()
-            }
-            else
+        action_snooze.connect_activate(move |_, param| {
+            if let Some(id) = param.and_then(|x| x.get::<u32>())
             {
-                time_now.naive_local()
+                let mut app_info = app_info.borrow_mut();
+
+                let at = app_info.time_events() + TimeDelta::minutes(ALARM_SNOOZE_MINUTES);
+
+                app_info.alarm_snoozes.push((id, at));
+                app.withdraw_notification(&alarm_notification_id(id));
             }
-        }
-        else
-        {
-            let tz: Result<chrono_tz::Tz, _> = time_zone.parse();
+        });
+    }
+
+    let action_dismiss = gtk::gio::SimpleAction::new(ACTION_ALARM_DISMISS, Some(gtk::glib::VariantTy::UINT32));
+
+    {
+        let app = app.clone();
+        let app_info = app_info.clone();
 
-            match tz
+        action_dismiss.connect_activate(move |_, param| {
+            if let Some(id) = param.and_then(|x| x.get::<u32>())
             {
-                Ok(offset) => time_now.with_timezone(&offset).naive_local(),
-                _ => time_now.naive_local(),
+                app_info.borrow_mut().alarm_snoozes.retain(|x| x.0 != id);
+                app.withdraw_notification(&alarm_notification_id(id));
             }
-        }
-    };
+        });
+    }
 
-    let has_time_disp_st = app_info.time_disp_st.is_some();
+    app.add_action(&action_snooze);
+    app.add_action(&action_dismiss);
+}
 
-    let time_delta = (time_now_naive - app_info.time_disp).num_seconds();
+fn update_chimes(app_info: &mut AppInfo)
+{
+    // at the time of the first clock, not at the time of the move to the new time
+    let now = app_info.time_events();
 
-    if time_delta.abs() <= 10
+    if let Some(from) = app_info.chime_checked.replace(now)
+        && let Some(chime) = app_info
+            .chime
+            .check(&from, &now, TimeDelta::seconds(CHIME_MAX_GAP_SECS))
     {
-        app_info.time_disp = time_now_naive;
-        app_info.time_disp_st = None;
+        ring_chime(&app_info.chime, &chime);
     }
-    else
-    {
-        if app_info.time_disp_st.is_none()
-        {
-            app_info.time_disp_st = Some((app_info.time_disp, time_now.to_utc()));
-        }
-
-        let (time_disp_st, time_st) = app_info.time_disp_st.unwrap();
+}
 
-        let timestamp_disp_du = TimeDelta::seconds(MOVE_FAST_SECS);
+fn ring_chime(config: &ChimeConfig, chime: &Chime)
+{
+    info!("chime {} {}", chime.kind.as_str(), chime.time);
 
-        let mut tweener =
-            tween::Tweener::quad_in_out(0.0, 1.0, timestamp_disp_du.num_milliseconds());
+    if let Some(command) = config.command.as_ref().filter(|x| x.trim() != "")
+    {
+        let command = chime.expand_command(command);
 
-        let timestamp_disp_diff = time_now.to_utc() - time_st;
+        let mut process = if cfg!(windows)
+        {
+            let mut x = std::process::Command::new("cmd");
+            x.arg("/C");
+            x
+        }
+        else
+        {
+            let mut x = std::process::Command::new("sh");
+            x.arg("-c");
+            x
+        };
 
-        let pos = tweener.move_to(timestamp_disp_diff.num_milliseconds());
+        process.arg(&command);
 
-        if pos < 0.0 || pos >= 1.0
+        for (key, _, value) in chime.vars()
         {
-            app_info.time_disp = time_now_naive;
-            app_info.time_disp_st = None;
+            process.env(key, value);
         }
-        else
+
+        match process.spawn()
         {
-            let add = TimeDelta::milliseconds(
-                ((time_now_naive - time_disp_st).num_milliseconds() as f64 * pos) as i64,
-            );
-            app_info.time_disp = time_disp_st + add;
+            Ok(mut child) =>
+            {
+                // not to leave a zombie
+                std::thread::spawn(move || child.wait());
+            },
+            Err(err) =>
+            {
+                error!("chime : `{}` : {}", command, err);
+            },
         }
     }
 
-    if has_time_disp_st != app_info.time_disp_st.is_some()
+    if config.notify
+        && let Some(app) = gtk::gio::Application::default()
     {
-        // update timer
-
-        let is_fast = app_info.time_disp_st.is_some();
-
-        {
-            let da = da.clone();
+        let notification = gtk::gio::Notification::new(GTK_APPLICATION_TITLE);
 
-            let old_timer_sourceid =
-                app_info
-                    .timer_sourceid
-                    .replace(Some(gtk::glib::source::timeout_add_local(
-                        std::time::Duration::from_millis(get_timer_interval(is_fast)),
-                        move || {
-                            da.queue_draw();
-                            gtk::glib::ControlFlow::Continue
-                        },
-                    )));
+        notification.set_body(Some(&chime.time.format("%H:%M").to_string()));
 
-            if let Some(sourceid) = old_timer_sourceid
-            {
-                sourceid.remove();
-            }
-        }
+        app.send_notification(Some("chime"), &notification);
     }
 }
 
-fn update_region<'a>(
-    window: &'a ApplicationWindow,
-    image_info: &'a ImageInfo,
-    app_info: &'a mut AppInfo,
-)
+fn update_region(window: &ApplicationWindow, image_info: &ImageInfo, app_info: &mut AppInfo, clock_id: u32)
 {
-    if image_info.sz.x > 0 && image_info.sz.y > 0 && app_info.zoom > 0
+    let Some(clock) = app_info.clock(clock_id)
+    else
+    {
+        return;
+    };
+
+    if image_info.sz.x > 0 && image_info.sz.y > 0 && clock.zoom > 0
     {
-        let zoom_factor = app_info.zoom as f64 / 100.0;
+        let zoom_factor = clock.zoom as f64 / 100.0;
 
         let sz = DVec2::new(
             image_info.sz.x as f64 * zoom_factor,
             image_info.sz.y as f64 * zoom_factor,
         );
 
-        if app_info.zoom_update
+        if clock.zoom_update
         {
             window.resize(sz.x as i32, sz.y as i32);
-            window.shape_combine_region(make_region(&image_info, app_info).as_ref());
-            app_info.zoom_update = false;
+            window.shape_combine_region(make_region(image_info, app_info, clock).as_ref());
+
+            if let Some(clock) = app_info.clock_mut(clock_id)
+            {
+                clock.zoom_update = false;
+            }
         }
         else if let Some(x) = image_info.config.enable_update_region_every_time
             && x
         {
-            window.shape_combine_region(make_region(&image_info, app_info).as_ref());
+            window.shape_combine_region(make_region(image_info, app_info, clock).as_ref());
         }
     }
 }
 
-fn make_region(image_info: &ImageInfo, app_info: &AppInfo) -> Option<Region>
+fn make_region(image_info: &ImageInfo, app_info: &AppInfo, clock: &ClockInfo) -> Option<Region>
 {
-    let zoom_factor = app_info.zoom as f64 / 100.0;
+    let zoom_factor = clock.zoom as f64 / 100.0;
 
     let sz = DVec2::new(
         image_info.sz.x as f64 * zoom_factor,
@@ -1629,7 +3070,7 @@ fn make_region(image_info: &ImageInfo, app_info: &AppInfo) -> Option<Region>
         let surface_mask = ImageSurface::create(Format::A8, sz.x as i32, sz.y as i32).unwrap();
         let cctx = Context::new(&surface_mask).unwrap();
 
-        draw_watch(&cctx, image_info, app_info, true);
+        draw_watch(&cctx, image_info, app_info, clock, true);
 
         surface_mask.create_region()
     }
@@ -1639,9 +3080,70 @@ fn make_region(image_info: &ImageInfo, app_info: &AppInfo) -> Option<Region>
     }
 }
 
-fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_region: bool)
+fn parse_color(color: &str) -> Option<(f64, f64, f64, f64)>
+{
+    // "#rrggbb" or "#rrggbbaa"
+
+    let hex = color.trim().strip_prefix('#')?;
+
+    if (hex.len() != 6 && hex.len() != 8) || !hex.is_ascii()
+    {
+        return None;
+    }
+
+    let mut ret = [1.0; 4];
+
+    for (i, x) in ret.iter_mut().enumerate().take(hex.len() / 2)
+    {
+        *x = u8::from_str_radix(&hex[i * 2 .. i * 2 + 2], 16).ok()? as f64 / 255.0;
+    }
+
+    Some((ret[0], ret[1], ret[2], ret[3]))
+}
+
+fn make_sun_text(kw: &str, app_info: &AppInfo, clock: &ClockInfo) -> Option<String>
+{
+    let (lat, lon) = app_info.location()?;
+
+    let date = clock.time_disp.date();
+
+    let fn_format = |utc: NaiveDateTime| (utc + clock.time_disp_offset).format("%H:%M").to_string();
+
+    let (altitude, is_rise) = match kw
+    {
+        "solar_noon" => return Some(fn_format(solar_noon(&date, lon))),
+        "day_length" =>
+        {
+            let secs = match sun_rise_set(&date, lat, lon, SUN_ALTITUDE_SUNRISE)
+            {
+                SunRiseSet::Normal(rise, set) => (set - rise).num_seconds(),
+                SunRiseSet::AlwaysAbove => 24 * 60 * 60,
+                SunRiseSet::AlwaysBelow => 0,
+            };
+
+            return Some(format!("{:02}:{:02}", secs / 3600, secs / 60 % 60));
+        },
+        "sunrise" => (SUN_ALTITUDE_SUNRISE, true),
+        "sunset" => (SUN_ALTITUDE_SUNRISE, false),
+        "civil_dawn" => (SUN_ALTITUDE_CIVIL, true),
+        "civil_dusk" => (SUN_ALTITUDE_CIVIL, false),
+        "nautical_dawn" => (SUN_ALTITUDE_NAUTICAL, true),
+        "nautical_dusk" => (SUN_ALTITUDE_NAUTICAL, false),
+        "astronomical_dawn" => (SUN_ALTITUDE_ASTRONOMICAL, true),
+        "astronomical_dusk" => (SUN_ALTITUDE_ASTRONOMICAL, false),
+        _ => return None,
+    };
+
+    match sun_rise_set(&date, lat, lon, altitude)
+    {
+        SunRiseSet::Normal(rise, set) => Some(fn_format(if is_rise { rise } else { set })),
+        _ => Some(String::from("--:--")),
+    }
+}
+
+fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, clock: &ClockInfo, for_region: bool)
 {
-    let zoom_factor = app_info.zoom as f64 / 100.0;
+    let zoom_factor = clock.zoom as f64 / 100.0;
 
     let sz = DVec2::new(
         image_info.sz.x as f64 * zoom_factor,
@@ -1660,11 +3162,6 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
         y: sz.y * (image_info.center.y / image_info.viewbox_sz.y),
     };
 
-    let center_sub_second = DVec2 {
-        x: sz.x * (image_info.center_sub_second.x / image_info.viewbox_sz.x),
-        y: sz.y * (image_info.center_sub_second.y / image_info.viewbox_sz.y),
-    };
-
     let func_render_rotate = |svg_handle: &SvgHandle, _center: &DVec2, angle: f64| {
         let _ = cctx.save();
 
@@ -1690,9 +3187,9 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
         Local::now()
     };
 
-    let time_secs = app_info.time_disp.hour12().1 * 60 * 60
-        + app_info.time_disp.minute() * 60
-        + app_info.time_disp.second();
+    let time_secs = clock.time_disp.hour12().1 * 60 * 60
+        + clock.time_disp.minute() * 60
+        + clock.time_disp.second();
 
     let angle_hour = time_secs as f64 / (12.0 * 60.0 * 60.0) * 360.0;
     let angle_min = time_secs as f64 / (60.0 * 60.0) * 360.0;
@@ -1707,6 +3204,8 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
     };
     let angle_sec = (time_now.second() as f64 + angle_sec_delta) / 60.0 * 360.0;
 
+    let chrono_elapsed = app_info.stopwatch.elapsed(Instant::now());
+
     // paint base BLACK ( for not region )
     if !for_region
     {
@@ -1719,17 +3218,264 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
         cctx.set_source_rgb(0.0, 0.0, 0.0);
         let _ = cctx.fill();
     }
-
-    // render base
-    if let Some(svgh) = image_info.svgh_base.as_ref()
+
+    // render base
+    if let Some(svgh) = image_info.svgh_base.as_ref()
+    {
+        let svg_renderer = rsvg::CairoRenderer::new(svgh);
+        svg_renderer.render_document(cctx, &viewport).unwrap();
+    }
+
+    // render wheels
+    for wheel in &image_info.wheels
+    {
+        let dt = &clock.time_disp;
+
+        let steps = match wheel.steps
+        {
+            Some(x) => x as f64,
+            None => wheel.driver.range(dt),
+        };
+
+        let position = wheel.driver.step_position(dt, wheel.duration);
+
+        let direction = if wheel.clockwise { 1.0 } else { -1.0 };
+
+        let angle = wheel.start_angle + direction * position / steps * 360.0;
+
+        let center = DVec2 {
+            x: sz.x * (wheel.center.x / image_info.viewbox_sz.x),
+            y: sz.y * (wheel.center.y / image_info.viewbox_sz.y),
+        };
+
+        if let Some(svgh_window) = wheel.svgh_window.as_ref()
+        {
+            // draw the wheel through the window
+
+            cctx.push_group();
+            func_render_rotate(&wheel.svgh, &center, angle);
+            let pattern_wheel = cctx.pop_group();
+
+            cctx.push_group();
+            func_render(svgh_window);
+            let pattern_window = cctx.pop_group();
+
+            if let Ok(pattern_wheel) = pattern_wheel
+                && let Ok(pattern_window) = pattern_window
+            {
+                let _ = cctx.set_source(&pattern_wheel);
+                let _ = cctx.mask(&pattern_window);
+            }
+        }
+        else
+        {
+            func_render_rotate(&wheel.svgh, &center, angle);
+        }
+
+        if let Some(svgh) = wheel.svgh_aperture.as_ref()
+        {
+            func_render(svgh);
+        }
+    }
+
+    // render moon_phase_disc
+    if let Some(svgh) = image_info.svgh_moon_phase_disc.as_ref()
+    {
+        /*
+            the traditional moon phase disc with two moons
+            drawn at the new moon ( the moons are hidden by the aperture ), turns 180 degrees per lunation
+        */
+
+        let moon = moon_phase(&(clock.time_disp - clock.time_disp_offset));
+
+        let center_moon_phase = DVec2 {
+            x: sz.x * (image_info.center_moon_phase.x / image_info.viewbox_sz.x),
+            y: sz.y * (image_info.center_moon_phase.y / image_info.viewbox_sz.y),
+        };
+
+        func_render_rotate(svgh, &center_moon_phase, moon.phase * 180.0);
+
+        // render moon_phase_aperture
+        if let Some(svgh) = image_info.svgh_moon_phase_aperture.as_ref()
+        {
+            func_render(svgh);
+        }
+    }
+
+    // render daylight arc
+    if app_info.enable_daylight_arc
+        && let Some(face) = image_info.config.daylight_arc
+        && let Some((lat, lon)) = app_info.location()
+    {
+        let dt = &clock.time_disp;
+
+        let span = match sun_rise_set(&dt.date(), lat, lon, SUN_ALTITUDE_SUNRISE)
+        {
+            SunRiseSet::Normal(rise, set) =>
+            {
+                let rise = rise + clock.time_disp_offset;
+                let turn_secs = face.range(dt) * face.unit_secs(dt);
+
+                Some((
+                    face.fraction(&rise, true) * 360.0,
+                    ((set - rise).num_seconds() as f64 / turn_secs * 360.0).min(360.0),
+                ))
+            },
+            SunRiseSet::AlwaysAbove => Some((0.0, 360.0)),
+            SunRiseSet::AlwaysBelow => None,
+        };
+
+        if let Some((start, span)) = span
+        {
+            let scale = sz.x / image_info.viewbox_sz.x;
+
+            let radius = image_info
+                .config
+                .daylight_arc_radius
+                .unwrap_or(image_info.viewbox_sz.x.min(image_info.viewbox_sz.y) * 0.45);
+            let width = image_info.config.daylight_arc_width.unwrap_or(radius * 0.05);
+            let (r, g, b, a) = image_info
+                .config
+                .daylight_arc_color
+                .as_deref()
+                .and_then(parse_color)
+                .unwrap_or((1.0, 0.785, 0.0, 0.5));
+
+            // 0 = 12 o'clock, clockwise -> cairo 0 = 3 o'clock
+            let start = start + image_info.config.daylight_arc_offset.unwrap_or(0.0) - 90.0;
+
+            let _ = cctx.save();
+
+            cctx.new_path();
+            cctx.arc(
+                center.x,
+                center.y,
+                radius * scale,
+                start.to_radians(),
+                (start + span).to_radians(),
+            );
+            cctx.set_line_width(width * scale);
+            cctx.set_source_rgba(r, g, b, a);
+            let _ = cctx.stroke();
+
+            let _ = cctx.restore();
+        }
+    }
+
+    // render countdown arc. the remaining time from 12 o'clock, clockwise
+    if let Some(remaining) = app_info.countdown.remaining(&Utc::now())
+        && image_info.svgh_countdown_handle.is_none()
+        && image_info.config.countdown_arc.unwrap_or(true)
+        && !remaining.is_zero()
+    {
+        let scale = sz.x / image_info.viewbox_sz.x;
+
+        let radius = image_info
+            .config
+            .countdown_arc_radius
+            .unwrap_or(image_info.viewbox_sz.x.min(image_info.viewbox_sz.y) * 0.3);
+        let width = image_info.config.countdown_arc_width.unwrap_or(radius * 0.2);
+        let (r, g, b, a) = image_info
+            .config
+            .countdown_arc_color
+            .as_deref()
+            .and_then(parse_color)
+            .unwrap_or((1.0, 0.25, 0.25, 0.5));
+
+        let span = app_info.countdown.fraction(&Utc::now()) * 360.0;
+
+        let _ = cctx.save();
+
+        cctx.new_path();
+        cctx.arc(
+            center.x,
+            center.y,
+            radius * scale,
+            (-90.0_f64).to_radians(),
+            (span - 90.0).to_radians(),
+        );
+        cctx.set_line_width(width * scale);
+        cctx.set_source_rgba(r, g, b, a);
+        let _ = cctx.stroke();
+
+        let _ = cctx.restore();
+    }
+
+    // render sub_base_text
+
+    let with_text_time_zone = if let Some(x) = image_info.config.with_text_time_zone
+        && x
+    {
+        true
+    }
+    else
+    {
+        false
+    };
+    let with_text_date = if let Some(x) = image_info.config.with_text_date
+        && x
+    {
+        true
+    }
+    else
+    {
+        false
+    };
+    let with_text_time = if let Some(x) = image_info.config.with_text_time
+        && x
+    {
+        true
+    }
+    else
+    {
+        false
+    };
+    let with_text_segment = if let Some(x) = image_info.config.with_text_segment
+        && x
+    {
+        true
+    }
+    else
+    {
+        false
+    };
+    let with_text_moon = if let Some(x) = image_info.config.with_text_moon
+        && x
+    {
+        true
+    }
+    else
+    {
+        false
+    };
+    let with_text_sun = if let Some(x) = image_info.config.with_text_sun
+        && x
+    {
+        true
+    }
+    else
+    {
+        false
+    };
+    let with_text_chrono = if let Some(x) = image_info.config.with_text_chrono
+        && x
+    {
+        true
+    }
+    else
     {
-        let svg_renderer = rsvg::CairoRenderer::new(svgh);
-        svg_renderer.render_document(cctx, &viewport).unwrap();
+        false
+    };
+    let with_text_timer = if let Some(x) = image_info.config.with_text_timer
+        && x
+    {
+        true
     }
-
-    // render sub_base_text
-
-    let with_text_time_zone = if let Some(x) = image_info.config.with_text_time_zone
+    else
+    {
+        false
+    };
+    let with_text_calendar = if let Some(x) = image_info.config.with_text_calendar
         && x
     {
         true
@@ -1738,7 +3484,7 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
     {
         false
     };
-    let with_text_date = if let Some(x) = image_info.config.with_text_date
+    let with_text_digit = if let Some(x) = image_info.config.with_text_digit
         && x
     {
         true
@@ -1747,7 +3493,7 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
     {
         false
     };
-    let with_text_time = if let Some(x) = image_info.config.with_text_time
+    let with_text_digit_transition = if let Some(x) = image_info.config.with_text_digit_transition
         && x
     {
         true
@@ -1756,7 +3502,7 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
     {
         false
     };
-    let with_text_segment = if let Some(x) = image_info.config.with_text_segment
+    let with_text_word_clock = if let Some(x) = image_info.config.with_text_word_clock
         && x
     {
         true
@@ -1766,11 +3512,29 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
         false
     };
 
-    if (with_text_time_zone || with_text_date || with_text_time || with_text_segment)
+    if (with_text_time_zone
+        || with_text_date
+        || with_text_time
+        || with_text_segment
+        || with_text_moon
+        || with_text_sun
+        || with_text_chrono
+        || with_text_timer
+        || with_text_calendar
+        || with_text_digit
+        || with_text_digit_transition
+        || with_text_word_clock)
         && let Some( template) = image_info.template_base_text.as_ref()
     {
+        static RE_DIGIT: LazyLock<regex::Regex> = LazyLock::new(|| {
+            regex::Regex::new(r"(?i)^digit_(hh|hl|mh|ml|sh|sl|yr1|yr2|yr3|yr4|moh|mol|dah|dal|dy1|dy2|dy3)_([0-9])$").unwrap()
+        });
+
+        static RE_WORD_CLOCK: LazyLock<regex::Regex> =
+            LazyLock::new(|| regex::Regex::new(r"(?i)^word_(?:(en|ja)_([a-z0-9]+)|dot([1-4]))$").unwrap());
+
         static RE_SEGMENT_NUM: LazyLock<regex::Regex> = LazyLock::new(|| {
-            regex::Regex::new(r"(?i)seg_(hh|hl|mh|ml|sh|sl)([a-g])").unwrap()
+            regex::Regex::new(r"(?i)seg_(hh|hl|mh|ml|sh|sl|yr1|yr2|yr3|yr4|moh|mol|dah|dal|dy1|dy2|dy3)([a-g])").unwrap()
         });
 
         static RE_SEGMENT_AMPM: LazyLock<regex::Regex> =
@@ -1779,6 +3543,28 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
         static RE_SEGMENT_DOT: LazyLock<regex::Regex> =
             LazyLock::new(|| regex::Regex::new(r"(?i)seg_dot").unwrap());
 
+        static RE_SEGMENT_ALNUM: LazyLock<regex::Regex> = LazyLock::new(|| {
+            regex::Regex::new(r"(?i)^alnum_(wn|mn|w|m)(\d+)_(?:seg_(a1|a2|d1|d2|g1|g2|[a-m])|dot_r([0-6])c([0-4]))$").unwrap()
+        });
+
+        let moon = if with_text_moon
+        {
+            Some(moon_phase(&(clock.time_disp - clock.time_disp_offset)))
+        }
+        else
+        {
+            None
+        };
+
+        let calendar = if with_text_calendar
+        {
+            Some(Calendar::new(&clock.time_disp, clock.time_disp_offset, &clock.time_zone))
+        }
+        else
+        {
+            None
+        };
+
         let mut ctx = svgclock_rs::minitemplate::Context::new();
 
         ctx.opt = VarOpt::RAW;
@@ -1788,169 +3574,297 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
             match kw.as_str()
             {
                 /* https://docs.rs/chrono/latest/chrono/format/strftime/index.html */
-                "time_zone" =>
+                "time_zone" | "time_zone2" =>
                 {
                     if with_text_time_zone && app_info.enable_text_time_zone
                     {
-                        ctx.set( &kw, app_info.time_zone.clone() );
+                        ctx.set( &kw, if kw == "time_zone" { clock.time_zone.clone() } else { clock.time_zone2.clone() } );
                     }
                 },
-                "date" =>
+                "date" | "date2" =>
                 {
                     if with_text_date && app_info.enable_text_date
                     {
-                        if app_info.text_format_date == AppInfoFormatDate::DtCustom
-                            && app_info.text_format_date_custom.is_some()
-                        {
-                            let df = app_info
-                                .time_disp
-                                .format(app_info.text_format_date_custom.as_ref().unwrap());
-
-                            let mut buffer = String::new();
+                        let dt = if kw == "date" { clock.time_disp } else { clock.time_disp2() };
 
-                            if let Ok(_) = df.write_to(&mut buffer)
-                            {
-                                ctx.set( &kw, buffer );
-                            }
-                        }
-                        else
+                        if let Some(x) = app_info.format_text_date(&dt)
                         {
-                            ctx.set( &kw,
-                                app_info
-                                    .time_disp
-                                    .format(app_info.text_format_date.format_str().0)
-                                    .to_string()
-                            );
+                            ctx.set( &kw, x );
                         }
                     }
                 },
-                "time" =>
+                "time" | "time2" =>
                 {
                     if with_text_time && app_info.enable_text_time
                     {
-                        if app_info.text_format_time == AppInfoFormatTime::TmCustom
-                            && app_info.text_format_time_custom.is_some()
+                        let dt = if kw == "time" { clock.time_disp } else { clock.time_disp2() };
+
+                        if let Some(x) = app_info.format_text_time(&dt)
                         {
-                            let df = app_info
-                                .time_disp
-                                .format(app_info.text_format_time_custom.as_ref().unwrap());
+                            ctx.set( &kw, x );
+                        }
+                    }
+                },
+                "moon_phase" | "moon_age" | "moon_illumination" =>
+                {
+                    /*
+                        {{moon_phase}}          "New Moon", "Waxing Crescent", "First Quarter", ...
+                        {{moon_age}}            days since the new moon. "12.3"
+                        {{moon_illumination}}   illuminated fraction in percent. "87"
+                    */
+
+                    if let Some(moon) = moon.as_ref()
+                    {
+                        ctx.set( &kw,
+                            match kw.as_str()
+                            {
+                                "moon_phase" => String::from(moon.name()),
+                                "moon_age" => format!("{:.1}", moon.age),
+                                _ => format!("{:.0}", moon.illumination * 100.0),
+                            }
+                        );
+                    }
+                },
+                "sunrise" | "sunset" | "solar_noon" | "day_length" | "civil_dawn" | "civil_dusk"
+                | "nautical_dawn" | "nautical_dusk" | "astronomical_dawn" | "astronomical_dusk" =>
+                {
+                    /*
+                        {{sunrise}}, {{sunset}}, {{solar_noon}}     "05:45"  "--:--" ( polar day / night )
+                        {{civil_dawn}}, {{civil_dusk}}, {{nautical_dawn}}, {{nautical_dusk}},
+                        {{astronomical_dawn}}, {{astronomical_dusk}}
+                        {{day_length}}                              "12:08"
+                    */
+
+                    if with_text_sun && let Some(x) = make_sun_text(&kw, app_info, clock)
+                    {
+                        ctx.set( &kw, x );
+                    }
+                },
+                "timer_remaining" =>
+                {
+                    /*
+                        {{timer_remaining}}     the remaining time of the timer. "04:59"  "1:30:00"  "" ( not running )
+                    */
 
-                            let mut buffer = String::new();
+                    if with_text_timer
+                    {
+                        ctx.set( &kw,
+                            app_info.countdown.remaining(&Utc::now()).map(format_remaining).unwrap_or_default()
+                        );
+                    }
+                },
+                x if CALENDAR_VARS.contains(&x) =>
+                {
+                    /*
+                        {{year}}, {{month}}, {{day}}, {{weekday}}, {{month_name}}, {{weekday_name}}, ...
+                        see calendar.rs
+                    */
+
+                    if let Some(calendar) = calendar.as_ref()
+                        && let Some(x) = calendar.var(x)
+                    {
+                        ctx.set( &kw, x );
+                    }
+                },
+                "chrono_elapsed" | "chrono_laps" | "chrono_lap" =>
+                {
+                    /*
+                        {{chrono_elapsed}}  the time of the stopwatch. "00:01:23.4"
+                        {{chrono_laps}}     the number of the laps. "3"
+                        {{chrono_lap}}      the time of the last lap. "00:00:41.2"  "" ( no lap )
+                    */
 
-                            if let Ok(_) = df.write_to(&mut buffer)
+                    if with_text_chrono
+                    {
+                        ctx.set( &kw,
+                            match kw.as_str()
                             {
-                                ctx.set( &kw, buffer );
+                                "chrono_elapsed" => format_elapsed(chrono_elapsed),
+                                "chrono_laps" => app_info.stopwatch.laps().len().to_string(),
+                                _ => app_info.stopwatch.last_lap().map(format_elapsed).unwrap_or_default(),
                             }
+                        );
+                    }
+                },
+                _ =>
+                {
+                    if with_text_word_clock && let Some(caps) = RE_WORD_CLOCK.captures(&kw)
+                    {
+                        /*
+                            <g visibility="{{word_en_half}}"></g>
+                            <g visibility="{{word_ja_h10}}"></g>
+                            <g visibility="{{word_dot1}}"></g>
+
+                            {{word_xxx}} = "visible" or "hidden"
+
+                            $1 = en, ja
+                            $2 = the word. see wordclock.rs
+                            $3 = 1 .. 4. the dots of the minutes after the five minutes
+                        */
+
+                        let resolution = image_info.config.word_clock_resolution.unwrap_or(5);
+
+                        let is_on = if let Some(dot) = caps.get(3)
+                        {
+                            dot.as_str().parse::<u32>().is_ok_and(|x| x <= word_clock_dots(&clock.time_disp, resolution))
                         }
                         else
                         {
-                            ctx.set( &kw,
-                                app_info
-                                    .time_disp
-                                    .format(app_info.text_format_time.format_str().0)
-                                    .to_string()
-                            );
+                            let word = caps.get(2).unwrap().as_str().to_ascii_lowercase();
+
+                            WordClockLang::from_name(caps.get(1).unwrap().as_str())
+                                .is_some_and(|lang| word_clock_words(lang, &clock.time_disp, resolution).contains(&word.as_str()))
+                        };
+
+                        ctx.set( &kw, if is_on { "visible" } else { "hidden" } );
+                    }
+                    else if with_text_digit_transition && let Some(caps) = RE_DIGIT_TRANSITION.captures(&kw)
+                    {
+                        /*
+                            the outgoing ( from ) and the incoming ( to ) digit while the digit changes.
+                            DIGIT_TRANSITION_MILLIS
+
+                            {{digit_hh_from}}, {{digit_hh_to}}                  = "0" .. "9"
+                            {{digit_hh_from_(0-9)}}, {{digit_hh_to_(0-9)}}      = "visible" or "hidden". glyphs
+                            {{digit_hh_from_seg_(a-g)}}, {{digit_hh_to_seg_(a-g)}} = "visible" or "hidden". seven segments
+                            {{digit_hh_progress}}                               = "0.000" .. "1.000" ( eased )
+
+                            when the digit is not changing, from = to and progress = 1
+                        */
+
+                        let position = caps.get(1).unwrap().as_str();
+                        let kind = caps.get(2).unwrap().as_str().to_ascii_lowercase();
+                        let hour12 = app_info.enable_text_time_segment_hour12;
+
+                        let (time_from, progress) = clock.digit_transition().unwrap_or((clock.time_disp, 1.0));
+
+                        let num_from = digit(&time_from, position, hour12);
+                        let num_to = digit(&clock.time_disp, position, hour12);
+
+                        let num = if kind == "from" { num_from } else { num_to };
+
+                        if kind == "progress"
+                        {
+                            ctx.set( &kw, format!("{:.3}", if num_from == num_to { 1.0 } else { progress }) );
+                        }
+                        else if let Some(x) = caps.get(3)
+                        {
+                            let is_on = num.is_some_and(|num| x.as_str().parse::<u32>() == Ok(num));
+
+                            ctx.set( &kw, if is_on { "visible" } else { "hidden" } );
+                        }
+                        else if let Some(x) = caps.get(4)
+                        {
+                            let is_on = num
+                                .and_then(|num| char::from_digit(num, 10))
+                                .is_some_and(|c| seven_segment_is_on(c, x.as_str().chars().next().unwrap_or(' ')));
+
+                            ctx.set( &kw, if is_on { "visible" } else { "hidden" } );
+                        }
+                        else
+                        {
+                            ctx.set( &kw, num.map(|x| x.to_string()).unwrap_or_default() );
                         }
                     }
-                },
-                _ =>
-                {
-                    if with_text_segment && app_info.enable_text_time_segment
+                    else if with_text_digit && let Some(caps) = RE_DIGIT.captures(&kw)
+                    {
+                        /*
+                            <g visibility="{{digit_(hh|hl|mh|...)_([0-9])}}"></g>
+                            ex.
+                            <g visibility="{{digit_hh_0}}"></g> .. <g visibility="{{digit_hh_9}}"></g>
+
+                            {{digit_xxx_n}} = "visible" for the glyph of the current digit, "hidden" for the others
+
+                            $1 = the digit position. see segment.rs
+                            $2 = 0 .. 9
+                        */
+
+                        let num = digit(&clock.time_disp, caps.get(1).unwrap().as_str(), app_info.enable_text_time_segment_hour12);
+                        let is_on = num.is_some_and(|x| caps.get(2).unwrap().as_str().parse::<u32>() == Ok(x));
+
+                        ctx.set( &kw, if is_on { "visible" } else { "hidden" } );
+                    }
+                    else if with_text_segment && app_info.enable_text_time_segment
                     {
-                        if let Some(caps) = RE_SEGMENT_NUM.captures(&kw )
+                        if let Some(caps) = RE_SEGMENT_ALNUM.captures(&kw)
+                        {
+                            /*
+                                <g visibility="{{alnum_(wn|mn|w|m)(index)_seg_(segment)}}"></g>
+                                <g visibility="{{alnum_(wn|mn|w|m)(index)_dot_r(row)c(col)}}"></g>
+                                ex.
+                                <g visibility="{{alnum_w0_seg_a1}}"></g>
+                                <g visibility="{{alnum_m2_dot_r6c4}}"></g>
+
+                                {{alnum_xxx}} = "visible" or "hidden"
+
+                                $1 = (wn|mn|w|m)
+                                    w  -> Weekday abbreviation ( "MON" )
+                                    m  -> Month abbreviation ( "OCT" )
+                                    wn -> Weekday name ( "MONDAY" )
+                                    mn -> Month name ( "OCTOBER" )
+                                $2 = the index of the letter. 0 = the first. over the length = blank
+
+                                seg_(segment) = a1,a2,b,c,d1,d2,e,f,g1,g2,h,i,j,k,l,m ( sixteen segments )
+                                                a,d ( fourteen segments )
+                                dot_r(row)c(col) = r0 .. r6, c0 .. c4 ( 5 x 7 dot matrix )
+                                    see segment.rs
+                            */
+
+                            let format = match caps.get(1).unwrap().as_str().to_ascii_lowercase().as_str()
+                            {
+                                "w" => "%a",
+                                "m" => "%b",
+                                "wn" => "%A",
+                                _ => "%B",
+                            };
+
+                            let c = caps
+                                .get(2)
+                                .and_then(|x| x.as_str().parse::<usize>().ok())
+                                .and_then(|x| clock.time_disp.format(format).to_string().chars().nth(x))
+                                .unwrap_or(' ');
+
+                            let is_on = if let Some(segment) = caps.get(3)
+                            {
+                                sixteen_segment_is_on(c, segment.as_str())
+                            }
+                            else
+                            {
+                                let row = caps.get(4).map_or(0, |x| x.as_str().parse::<usize>().unwrap_or(0));
+                                let col = caps.get(5).map_or(0, |x| x.as_str().parse::<usize>().unwrap_or(0));
+
+                                dot_matrix_is_on(c, row, col)
+                            };
+
+                            ctx.set( &kw, if is_on { "visible" } else { "hidden" } );
+                        }
+                        else if let Some(caps) = RE_SEGMENT_NUM.captures(&kw )
                         {
                             /*
-                                <g visibility="{{seg_(hh|hl|mh|ml|sh|sl)([a-g])}}"></g>
+                                <g visibility="{{seg_(hh|hl|mh|ml|sh|sl|yr1|...)([a-g])}}"></g>
                                 ex.
                                 <g visibility="{{seg_hha}}"></g>
 
                                 {{seg_xxx}} = "visible" or "hidden"
 
-                                $1 = (hh|hl|mh|ml|sh|sl)
-                                    hh -> Hour High digit
-                                    hl -> Hour Low digit
-                                    mh -> Minute High digit
-                                    ml -> Minute Low digit
-                                    sh -> Second High digit
-                                    sl -> Second Low digit
+                                $1 = (hh|hl|mh|ml|sh|sl|yr1|yr2|yr3|yr4|moh|mol|dah|dal|dy1|dy2|dy3)
+                                    see segment.rs
 
                                 $2 = a,b,c,d,e,f,g
-                                    segment https://en.wikipedia.org/wiki/Seven-segment_display#/media/File:7_Segment_Display_with_Labeled_Segments.svg
-                                       =a=
-                                    |f|   |b|
-                                       =g=
-                                    |e|   |c|
-                                       =d=
+                                    see segment.rs
                             */
 
                             let m1 = caps.get(1).unwrap();
                             let m2 = caps.get(2).unwrap();
 
-                            let flag_12 = app_info.enable_text_time_segment_hour12;
-
-                            let num = match m1.as_str()
-                            {
-                                "hh" =>
-                                {
-                                    (if flag_12
-                                    {
-                                        app_info.time_disp.hour12().1
-                                    }
-                                    else
-                                    {
-                                        app_info.time_disp.hour()
-                                    }) / 10
-                                },
-                                "hl" =>
-                                {
-                                    (if flag_12
-                                    {
-                                        app_info.time_disp.hour12().1
-                                    }
-                                    else
-                                    {
-                                        app_info.time_disp.hour()
-                                    }) % 10
-                                },
-                                "mh" => app_info.time_disp.minute() / 10,
-                                "ml" => app_info.time_disp.minute() % 10,
-                                "sh" => app_info.time_disp.second() / 10,
-                                "sl" => app_info.time_disp.second() % 10,
-                                _ => 0,
-                            };
-
-                            // num -> seg
-                            // https://ja.wikipedia.org/wiki/7%E3%82%BB%E3%82%B0%E3%83%A1%E3%83%B3%E3%83%88%E3%83%87%E3%82%A3%E3%82%B9%E3%83%97%E3%83%AC%E3%82%A4#%E6%95%B0%E3%81%8B%E3%82%897%E3%82%BB%E3%82%B0%E3%83%A1%E3%83%B3%E3%83%88%E3%82%B3%E3%83%BC%E3%83%89%E3%81%B8%E3%81%AE%E5%A4%89%E6%8F%9B
-
-                            let b_on: u8 = match num
-                            {
-                                0 => 0x7e,
-                                1 => 0x30,
-                                2 => 0x6d,
-                                3 => 0x79,
-                                4 => 0x33,
-                                5 => 0x5b,
-                                6 => 0x5f,
-                                7 => 0x70,
-                                8 => 0x7f,
-                                9 => 0x7b,
-                                _ => 0x00,
-                            };
+                            let num = digit(&clock.time_disp, m1.as_str(), app_info.enable_text_time_segment_hour12);
 
-                            let b_mask: u8 = match m2.as_str()
-                            {
-                                "a" => 0x1 << 6,
-                                "b" => 0x1 << 5,
-                                "c" => 0x1 << 4,
-                                "d" => 0x1 << 3,
-                                "e" => 0x1 << 2,
-                                "f" => 0x1 << 1,
-                                "g" => 0x1 << 0,
-                                _ => 0x0u8,
-                            };
+                            let is_on = num
+                                .and_then(|x| char::from_digit(x, 10))
+                                .is_some_and(|c| seven_segment_is_on(c, m2.as_str().chars().next().unwrap_or(' ')));
 
                             ctx.set( &kw,
-                                if b_on & b_mask != 0x00
+                                if is_on
                                 {
                                     "visible"
                                 }
@@ -1959,18 +3873,6 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
                                     "hidden"
                                 },
                             );
-
-                            /*
-                            if log_enabled!(Level::Debug)
-                            {
-                                if m1.as_bytes() == b"sl"
-                                {
-                                    let c = String::from_utf8( m2.as_bytes().to_vec() );
-
-                                    debug!("{}:{:?}:{:02X}:{:02X}:{}", num, c, b_on, b_mask, b_on & b_mask );
-                                }
-                            }
-                            */
                         }
                         else if let Some(caps) = RE_SEGMENT_AMPM.captures(&kw)
                         {
@@ -1983,7 +3885,7 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
                                 {{seg_am}}, {{seg_pm}}, {{seg_amb}}, {{seg_pmb}} = "visible" or "hidden"
                             */
                             let is_enable = app_info.enable_text_time_segment_hour12;
-                            let is_pm = app_info.time_disp.hour() >= 12;
+                            let is_pm = clock.time_disp.hour() >= 12;
 
                             let m1 = caps.get(1).unwrap().as_str();
 
@@ -2014,7 +3916,7 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
 
                             let is_on = if app_info.enable_text_time_segment_dotblink
                             {
-                                app_info.time_disp.and_utc().timestamp_subsec_millis() < 500
+                                clock.time_disp.and_utc().timestamp_subsec_millis() < 500
                             }
                             else
                             {
@@ -2045,31 +3947,67 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
         }
     }
 
-    // render sub_second
-    if app_info.show_seconds
+    // the sub-dial of the sub_second_xxx layers replaces second_handle
+    let show_sub_second = app_info.show_seconds
         && app_info.enable_sub_second_handle
-        && image_info.svgh_sub_second_handle.is_some()
+        && image_info.sub_dials.iter().any(|x| x.is_second());
+
+    // render sub_dials
+    for sub_dial in &image_info.sub_dials
     {
-        // render sub_second_base
-        if let Some(svgh) = image_info.svgh_sub_second_base.as_ref()
+        if sub_dial.is_second() && !show_sub_second
         {
-            let svg_renderer = rsvg::CairoRenderer::new(svgh);
-            svg_renderer.render_document(cctx, &viewport).unwrap();
+            continue;
         }
 
-        // render sub_second_handle
-        if let Some(svgh) = image_info.svgh_sub_second_handle.as_ref()
+        let center = DVec2 {
+            x: sz.x * (sub_dial.center.x / image_info.viewbox_sz.x),
+            y: sz.y * (sub_dial.center.y / image_info.viewbox_sz.y),
+        };
+
+        let angle = match sub_dial.driver
         {
-            func_render_rotate(svgh, &center_sub_second, angle_sec);
+            // the same as second_handle
+            _ if sub_dial.is_second() => angle_sec,
+            SubDialDriver::Time(x) =>
+            {
+                let dt = &clock.time_disp;
+
+                sub_dial.range.angle_snap_back(
+                    x.value(dt, sub_dial.smooth),
+                    x.range(dt),
+                    x.elapsed_secs(dt),
+                )
+            },
+            SubDialDriver::Chrono(x) =>
+            {
+                // no snap back while stopped
+                let elapsed_turn = if app_info.stopwatch.is_running()
+                {
+                    x.value(chrono_elapsed, true) * x.unit_secs()
+                }
+                else
+                {
+                    -1.0
+                };
+
+                sub_dial.range.angle_snap_back(x.value(chrono_elapsed, sub_dial.smooth), x.range(), elapsed_turn)
+            },
+        };
+
+        if let Some(svgh) = sub_dial.svgh_base.as_ref()
+        {
+            func_render(svgh);
         }
 
-        // render sub_second_center_circle
-        if let Some(svgh) = image_info.svgh_sub_second_center_circle.as_ref()
+        func_render_rotate(&sub_dial.svgh_handle, &center, angle);
+
+        if let Some(svgh) = sub_dial.svgh_center_circle.as_ref()
         {
             if let Some(x) = image_info.config.enable_rotate_center_circle
                 && x
             {
-                func_render_rotate(svgh, &center_sub_second, angle_sec);
+                func_render_rotate(svgh, &center, angle);
             }
             else
             {
@@ -2078,6 +4016,54 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
         }
     }
 
+    // render the hands declared in the config
+    for hand in &image_info.hands
+    {
+        let center = DVec2 {
+            x: sz.x * (hand.center.x / image_info.viewbox_sz.x),
+            y: sz.y * (hand.center.y / image_info.viewbox_sz.y),
+        };
+
+        let dt = &clock.time_disp;
+
+        let angle = hand.range.angle_snap_back(
+            hand.driver.value(dt, hand.smooth),
+            hand.driver.range(dt),
+            hand.driver.elapsed_secs(dt),
+        );
+
+        func_render_rotate(&hand.svgh, &center, angle);
+    }
+
+    // render countdown_handle. points at the remaining fraction of the duration
+    if let Some(svgh) = image_info.svgh_countdown_handle.as_ref()
+        && app_info.countdown.is_running()
+    {
+        func_render_rotate(svgh, &center, app_info.countdown.fraction(&Utc::now()) * 360.0);
+    }
+
+    // render alarm_handle. points at the next alarm like short_handle
+    // the alarms are in the time zone of the first clock
+    if let Some(svgh) = image_info.svgh_alarm_handle.as_ref()
+        && let Some((_, next)) = next_alarm(
+            &app_info.alarms,
+            &(clock.time_disp - clock.time_disp_offset + app_info.clock.time_disp_offset),
+        )
+    {
+        let next = next - app_info.clock.time_disp_offset + clock.time_disp_offset;
+        let secs = next.num_seconds_from_midnight() % (12 * 60 * 60);
+
+        func_render_rotate(svgh, &center, secs as f64 / (12.0 * 60.0 * 60.0) * 360.0);
+    }
+
+    // render gmt_handle. the time of the second time zone, one turn in 24 hours
+    if let Some(svgh) = image_info.svgh_gmt_handle.as_ref()
+    {
+        let secs = clock.time_disp2().num_seconds_from_midnight();
+
+        func_render_rotate(svgh, &center, secs as f64 / (24.0 * 60.0 * 60.0) * 360.0);
+    }
+
     // render short_handle
     if let Some(svgh) = image_info.svgh_short_handle.as_ref()
     {
@@ -2092,33 +4078,122 @@ fn draw_watch(cctx: &Context, image_info: &ImageInfo, app_info: &AppInfo, for_re
 
     // render second_handle
     if app_info.show_seconds
-        && ( !app_info.enable_sub_second_handle || image_info.svgh_sub_second_handle.is_none() )
+        && !show_sub_second
         && image_info.svgh_second_handle.is_some()
     {
-        if let Some(svgh) = image_info.svgh_second_handle.as_ref()
+        if let Some(svgh) = image_info.svgh_second_handle.as_ref()
+        {
+            func_render_rotate(svgh, &center, angle_sec);
+        }
+    }
+
+    // render the hands of the stopwatch
+    for (svgh, driver) in [
+        (&image_info.svgh_chrono_hour_handle, ChronoDriver::ChronoHour12),
+        (&image_info.svgh_chrono_minute_handle, ChronoDriver::ChronoMinute),
+        (&image_info.svgh_chrono_second_handle, ChronoDriver::ChronoSecond),
+    ]
+    {
+        if let Some(svgh) = svgh.as_ref()
+        {
+            func_render_rotate(svgh, &center, driver.value(chrono_elapsed, true) / driver.range() * 360.0);
+        }
+    }
+
+    // render center_circle
+    if let Some(svgh) = image_info.svgh_center_circle.as_ref()
+    {
+        if let Some(x) = image_info.config.enable_rotate_center_circle
+            && x
+        {
+            func_render_rotate(svgh, &center, angle_sec);
+        }
+        else
+        {
+            func_render(svgh);
+        }
+    }
+
+    // render countdown flash. 2 times per second over the drawn area
+    if !for_region
+        && let Some(x) = app_info.countdown_flash
+        && x.elapsed().as_secs_f64() < COUNTDOWN_FLASH_SECS
+        && x.elapsed().subsec_millis() < 250
+    {
+        let _ = cctx.save();
+
+        cctx.set_operator(gtk::cairo::Operator::Atop);
+        cctx.set_source_rgba(1.0, 1.0, 1.0, 0.6);
+        let _ = cctx.paint();
+
+        let _ = cctx.restore();
+    }
+
+    // render theme error. also into the region of the image for error ( no layers )
+    if (!for_region || image_info.svgh_base.is_none())
+        && let Some(err) = clock.theme_error.as_ref()
+    {
+        draw_message(cctx, &viewport, err);
+    }
+}
+
+fn draw_message(cctx: &Context, viewport: &Rectangle, message: &str)
+{
+    let font_size = (viewport.height() / 24.0).max(8.0);
+    let width_max = viewport.width() * 0.8;
+
+    let _ = cctx.save();
+
+    cctx.set_font_size(font_size);
+
+    // wrap by width
+
+    let mut lines = Vec::<String>::new();
+
+    for src_line in message.lines()
+    {
+        let mut line = String::new();
+
+        for c in src_line.chars()
         {
-            func_render_rotate(svgh, &center, angle_sec);
+            line.push(c);
+
+            if let Ok(ext) = cctx.text_extents(&line)
+                && ext.width() > width_max
+            {
+                line.pop();
+                lines.push(line);
+                line = String::from(c);
+            }
         }
+
+        lines.push(line);
     }
 
-    // render center_circle
-    if let Some(svgh) = image_info.svgh_center_circle.as_ref()
+    let line_height = font_size * 1.2;
+    let height = line_height * lines.len() as f64;
+
+    let x = (viewport.width() - width_max) / 2.0;
+    let y = (viewport.height() - height) / 2.0;
+
+    cctx.rectangle(x - font_size / 2.0, y - font_size / 2.0, width_max + font_size, height + font_size);
+    cctx.set_source_rgba(0.5, 0.0, 0.0, 0.8);
+    let _ = cctx.fill();
+
+    cctx.set_source_rgb(1.0, 1.0, 1.0);
+
+    for (i, line) in lines.iter().enumerate()
     {
-        if let Some(x) = image_info.config.enable_rotate_center_circle
-            && x
-        {
-            func_render_rotate(svgh, &center, angle_sec);
-        }
-        else
-        {
-            func_render(svgh);
-        }
+        cctx.move_to(x, y + line_height * (i as f64 + 1.0) - (line_height - font_size));
+        let _ = cctx.show_text(line);
     }
+
+    let _ = cctx.restore();
 }
 
-fn make_png_image(image_info: &ImageInfo, app_info: &mut AppInfo)
+fn make_png_image(image_info: &ImageInfo, app_info: &AppInfo, clock: &ClockInfo)
 {
-    let zoom_factor = app_info.zoom as f64 / 100.0;
+    let zoom_factor = clock.zoom as f64 / 100.0;
 
     let sz = DVec2::new(
         image_info.sz.x as f64 * zoom_factor,
@@ -2129,11 +4204,11 @@ fn make_png_image(image_info: &ImageInfo, app_info: &mut AppInfo)
 
     {
         let cctx = Context::new(&surface).unwrap();
-        draw_watch(&cctx, image_info, app_info, false);
+        draw_watch(&cctx, image_info, app_info, clock, false);
         surface.flush();
     }
 
-    if let Some(region) = make_region(image_info, app_info)
+    if let Some(region) = make_region(image_info, app_info, clock)
     {
         let h = surface.height();
         let w = surface.width();
@@ -2183,87 +4258,461 @@ fn make_png_image(image_info: &ImageInfo, app_info: &mut AppInfo)
     }
 }
 
-fn make_theme_menu(image_info: &Rc<RefCell<ImageInfo>>, app_info: &Rc<RefCell<AppInfo>>) -> Menu
+fn apply_theme(
+    image_info: &Rc<RefCell<ImageInfo>>,
+    app_info: &Rc<RefCell<AppInfo>>,
+    clock_id: u32,
+    id: &str,
+) -> bool
 {
-    let menu = Menu::new();
+    let entry = app_info.borrow().themes.get(id).cloned();
 
-    for ait in AppInfoTheme::iter()
+    let result = match entry
     {
-        let exist: bool = app_info.borrow().theme_names.contains_key(&ait);
+        Some(entry) => load_theme(&entry),
+        None => Err(format!("theme not found : {}", id).into()),
+    };
+
+    match result
+    {
+        Ok(_image_info) =>
+        {
+            let mut app_info = app_info.borrow_mut();
+
+            if let Some(clock) = app_info.clock_mut(clock_id)
+            {
+                clock.theme = String::from(id);
+                clock.theme_error = None;
+                // clock.zoom = 100;
+                clock.zoom_update = true;
+            }
+
+            if let Some(entry) = app_info.themes.get_mut(id)
+            {
+                entry.name = _image_info.config.get_theme_name();
+                entry.description = _image_info.config.get_theme_description();
+            }
+
+            image_info.replace(_image_info);
 
-        let name: String = if let Some(entry) = app_info.borrow().theme_names.get(&ait)
+            true
+        },
+        Err(err) =>
         {
-            if let Some(name) = &entry.0
-                && let Some(desc) = &entry.1
+            error!("theme {} : {}", id, err);
+
+            if let Some(clock) = app_info.borrow_mut().clock_mut(clock_id)
             {
-                format!("[{}] {} / {}", ait.to_string(), name, desc)
+                clock.theme_error = Some(err.to_string());
             }
-            else if let Some(name) = &entry.0
+
+            false
+        },
+    }
+}
+
+fn select_theme(image_info: &Rc<RefCell<ImageInfo>>, app_info: &Rc<RefCell<AppInfo>>, clock_id: u32, id: &str)
+{
+    if apply_theme(image_info, app_info, clock_id, id)
+    {
+        watch_theme(image_info, app_info, clock_id);
+    }
+}
+
+fn watch_theme(image_info: &Rc<RefCell<ImageInfo>>, app_info: &Rc<RefCell<AppInfo>>, clock_id: u32)
+{
+    // reload the current theme of the clock when its file is saved ( e.g. by Inkscape )
+
+    let path = {
+        let app_info = app_info.borrow();
+
+        match app_info.clock(clock_id).and_then(|x| app_info.themes.get(&x.theme))
+        {
+            Some(ThemeEntry {
+                source: ThemeSource::File(path),
+                ..
+            }) => Some(path.clone()),
+            _ => None,
+        }
+    };
+
+    let monitor = if let Some(path) = path
+    {
+        match gtk::gio::File::for_path(&path).monitor_file(
+            gtk::gio::FileMonitorFlags::WATCH_MOVES,
+            None::<&gtk::gio::Cancellable>,
+        )
+        {
+            Ok(monitor) =>
+            {
+                let image_info = image_info.clone();
+                let app_info = app_info.clone();
+
+                monitor.connect_changed(move |_, _, other_file, event| {
+                    debug!("watch_theme: {:?}", event);
+
+                    // Renamed: only when the file is renamed to the watched path ( not away from it )
+                    let is_target = || other_file.and_then(|x| x.path()).is_some_and(|x| x == path);
+
+                    match event
+                    {
+                        gtk::gio::FileMonitorEvent::ChangesDoneHint
+                        | gtk::gio::FileMonitorEvent::Created
+                        | gtk::gio::FileMonitorEvent::MovedIn =>
+                        {
+                            let id = app_info.borrow().clock(clock_id).map(|x| x.theme.clone());
+
+                            if let Some(id) = id
+                            {
+                                apply_theme(&image_info, &app_info, clock_id, &id);
+                            }
+                        },
+                        gtk::gio::FileMonitorEvent::Renamed if is_target() =>
+                        {
+                            let id = app_info.borrow().clock(clock_id).map(|x| x.theme.clone());
+
+                            if let Some(id) = id
+                            {
+                                apply_theme(&image_info, &app_info, clock_id, &id);
+                            }
+                        },
+                        _ =>
+                        {},
+                    }
+                });
+
+                Some(monitor)
+            },
+            Err(err) =>
+            {
+                error!("{:?} : {}", path, err);
+                None
+            },
+        }
+    }
+    else
+    {
+        None
+    };
+
+    let old_monitor = match app_info.borrow_mut().clock_mut(clock_id)
+    {
+        Some(clock) => std::mem::replace(&mut clock.theme_monitor, monitor),
+        None => monitor,
+    };
+
+    if let Some(old_monitor) = old_monitor
+    {
+        old_monitor.cancel();
+    }
+}
+
+fn make_theme_label(id: &str, entry: &ThemeEntry) -> String
+{
+    let id = match &entry.source
+    {
+        ThemeSource::File(path) if is_theme_id_custom(id) =>
+        {
+            if let Some(x) = path.file_name()
             {
-                format!("[{}] {}", ait.to_string(), name)
+                x.to_string_lossy().to_string()
             }
             else
             {
-                format!("[{}]", ait.to_string())
+                String::from(id)
+            }
+        },
+        _ => String::from(id),
+    };
+
+    if let Some(name) = &entry.name
+        && let Some(desc) = &entry.description
+    {
+        format!("[{}] {} / {}", id, name, desc)
+    }
+    else if let Some(name) = &entry.name
+    {
+        format!("[{}] {}", id, name)
+    }
+    else
+    {
+        format!("[{}]", id)
+    }
+}
+
+fn make_theme_menu(
+    window: &ApplicationWindow,
+    image_info: &Rc<RefCell<ImageInfo>>,
+    app_info: &Rc<RefCell<AppInfo>>,
+    clock_id: u32,
+) -> Menu
+{
+    let menu = Menu::new();
+
+    let themes: Vec<(String, ThemeEntry)> = app_info
+        .borrow()
+        .themes
+        .iter()
+        .map(|(id, entry)| (id.clone(), entry.clone()))
+        .collect();
+
+    let theme = app_info.borrow().clock(clock_id).map(|x| x.theme.clone());
+
+    let mut with_custom = false;
+
+    for (id, entry) in themes.iter()
+    {
+        if is_theme_id_custom(id) && !with_custom
+        {
+            with_custom = true;
+            menu.append(&SeparatorMenuItem::new());
+        }
+
+        let menu_item = CheckMenuItem::with_label(make_theme_label(id, entry).as_str());
+
+        menu_item.set_active(theme.as_ref() == Some(id));
+
+        if let ThemeSource::File(path) = &entry.source
+        {
+            menu_item.set_tooltip_text(Some(path.to_string_lossy().as_ref()));
+        }
+
+        {
+            let app_info = app_info.clone();
+            let image_info = image_info.clone();
+            let id = id.clone();
+
+            menu_item.connect_activate(move |_| {
+                select_theme(&image_info, &app_info, clock_id, &id);
+            });
+        }
+
+        menu.append(&menu_item);
+    }
+
+    menu.append(&SeparatorMenuItem::new());
+
+    let menu_item_add = MenuItem::with_label("Add theme…");
+
+    {
+        let window = window.clone();
+        let app_info = app_info.clone();
+        let image_info = image_info.clone();
+
+        menu_item_add.connect_activate(move |_| {
+            let dialog = FileChooserDialog::with_buttons(
+                Some("Add theme"),
+                Some(&window),
+                FileChooserAction::Open,
+                &[("_Cancel", ResponseType::Cancel), ("_Open", ResponseType::Accept)],
+            );
+
+            let filter = FileFilter::new();
+            filter.set_name(Some("SVG image (*.svg)"));
+            filter.add_pattern("*.svg");
+            filter.add_pattern("*.SVG");
+
+            dialog.add_filter(filter);
+            dialog.set_select_multiple(true);
+            dialog.set_keep_above(true);
+
+            {
+                let app_info = app_info.clone();
+                let image_info = image_info.clone();
+
+                dialog.connect_response(move |dialog, response| {
+                    if response == ResponseType::Accept
+                    {
+                        let mut ids = Vec::<String>::new();
+
+                        for path in dialog.filenames()
+                        {
+                            ids.push(
+                                app_info
+                                    .borrow_mut()
+                                    .add_theme_custom(path.to_string_lossy().as_ref()),
+                            );
+                        }
+
+                        if let Some(id) = ids.first()
+                        {
+                            select_theme(&image_info, &app_info, clock_id, id);
+                        }
+                    }
+
+                    dialog.close();
+                });
             }
+
+            dialog.show_all();
+        });
+    }
+
+    menu.append(&menu_item_add);
+
+    let menu_item_remove = MenuItem::with_label("Remove theme");
+    let menu_remove = Menu::new();
+
+    for (id, entry) in themes.iter().filter(|(id, _)| is_theme_id_custom(id))
+    {
+        let menu_item = MenuItem::with_label(make_theme_label(id, entry).as_str());
+
+        {
+            let app_info = app_info.clone();
+            let image_info = image_info.clone();
+            let id = id.clone();
+
+            menu_item.connect_activate(move |_| {
+                let is_current = app_info.borrow().clock(clock_id).is_some_and(|x| x.theme == id);
+
+                app_info
+                    .borrow_mut()
+                    .remove_theme_custom(id.trim_start_matches(THEME_ID_CUSTOM_PREFIX));
+
+                if is_current
+                {
+                    select_theme(&image_info, &app_info, clock_id, THEME_ID_DEFAULT);
+                }
+            });
+        }
+
+        menu_remove.append(&menu_item);
+    }
+
+    menu_item_remove.set_sensitive(with_custom);
+    menu_item_remove.set_submenu(Some(&menu_remove));
+
+    menu.append(&menu_item_remove);
+
+    menu
+}
+
+fn make_stopwatch_menu(da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>) -> Menu
+{
+    let menu = Menu::new();
+
+    let is_running = app_info.borrow().stopwatch.is_running();
+    let is_reset = app_info.borrow().stopwatch.is_reset();
+
+    let menu_item_start_stop = MenuItem::with_label(if is_running { "Stop" } else { "Start" });
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_start_stop.connect_activate(move |_| {
+            app_info.borrow_mut().stopwatch.toggle(Instant::now());
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_lap = MenuItem::with_label("Lap");
+
+    menu_item_lap.set_sensitive(is_running);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_lap.connect_activate(move |_| {
+            app_info.borrow_mut().stopwatch.lap(Instant::now());
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_reset = MenuItem::with_label("Reset");
+
+    menu_item_reset.set_sensitive(!is_running && !is_reset);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_reset.connect_activate(move |_| {
+            app_info.borrow_mut().stopwatch.reset();
+            da.queue_draw();
+        });
+    }
+
+    menu.append(&menu_item_start_stop);
+    menu.append(&menu_item_lap);
+    menu.append(&menu_item_reset);
+
+    menu
+}
+
+fn make_countdown_menu(window: &ApplicationWindow, da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>) -> Menu
+{
+    let menu = Menu::new();
+
+    for &x in COUNTDOWN_PRESETS.iter()
+    {
+        let label = format!("{} min", x);
+
+        let menu_item = MenuItem::with_label(label.as_str());
+
+        {
+            let da = da.clone();
+            let app_info = app_info.clone();
+
+            menu_item.connect_activate(move |_| {
+                let mut app_info = app_info.borrow_mut();
+                app_info.countdown.start(&Utc::now(), std::time::Duration::from_secs(x * 60));
+                app_info.countdown_flash = None;
+                da.queue_draw();
+            });
         }
-        else
-        {
-            format!("[{}]", ait.to_string())
-        };
 
-        let menu_item = CheckMenuItem::with_label(name.as_str());
+        menu.append(&menu_item);
+    }
 
-        menu_item.set_active(ait == app_info.borrow().theme);
-        menu_item.set_sensitive(exist);
+    let menu_item_custom = MenuItem::with_label("Custom…");
 
-        {
-            let app_info = app_info.clone();
-            let image_info = image_info.clone();
+    {
+        let window = window.clone();
+        let da = da.clone();
+        let app_info = app_info.clone();
 
-            menu_item.connect_activate(move |_| {
-                let theme_custom = app_info.borrow().theme_custom.clone();
+        menu_item_custom.connect_activate(move |_| {
+            show_countdown_dialog(&window, &da, &app_info);
+        });
+    }
 
-                match load_theme(ait, theme_custom)
-                {
-                    Some(_image_info) =>
-                    {
-                        let mut app_info = app_info.borrow_mut();
+    let menu_item_cancel = MenuItem::with_label("Cancel");
 
-                        app_info.theme = ait;
-                        // app_info.zoom = 100;
-                        app_info.zoom_update = true;
-                        app_info.theme_names.insert(
-                            ait,
-                            (
-                                _image_info.config.get_theme_name(),
-                                _image_info.config.get_theme_description(),
-                            ),
-                        );
+    menu_item_cancel.set_sensitive(app_info.borrow().countdown.is_running());
 
-                        image_info.replace(_image_info);
-                    },
-                    _ =>
-                    {},
-                }
-            });
-        }
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
 
-        menu.append(&menu_item);
+        menu_item_cancel.connect_activate(move |_| {
+            app_info.borrow_mut().countdown.cancel();
+            da.queue_draw();
+        });
     }
 
+    menu.append(&menu_item_custom);
+    menu.append(&SeparatorMenuItem::new());
+    menu.append(&menu_item_cancel);
+
     menu
 }
 
-fn make_zoom_menu(app_info: &Rc<RefCell<AppInfo>>) -> Menu
+fn make_zoom_menu(app_info: &Rc<RefCell<AppInfo>>, clock_id: u32) -> Menu
 {
     static ZOOMS: LazyLock<Vec<u32>> = LazyLock::new(|| (30 ..= 230).step_by(10).collect());
 
-    let zoom = app_info.borrow().zoom;
+    let mut zoom = app_info.borrow().clock(clock_id).map_or(100, |x| x.zoom);
 
     if ZOOMS.iter().find(|&&x| x == zoom).is_none()
     {
-        app_info.borrow_mut().zoom = 100;
+        zoom = 100;
+
+        if let Some(clock) = app_info.borrow_mut().clock_mut(clock_id)
+        {
+            clock.zoom = zoom;
+        }
     }
 
     let menu = Menu::new();
@@ -2274,15 +4723,17 @@ fn make_zoom_menu(app_info: &Rc<RefCell<AppInfo>>) -> Menu
 
         let menu_item = CheckMenuItem::with_label(label.as_str());
 
-        menu_item.set_active(app_info.borrow().zoom == x);
+        menu_item.set_active(zoom == x);
 
         {
             let app_info = app_info.clone();
 
             menu_item.connect_activate(move |_| {
-                let mut app_info = app_info.borrow_mut();
-                app_info.zoom = x;
-                app_info.zoom_update = true;
+                if let Some(clock) = app_info.borrow_mut().clock_mut(clock_id)
+                {
+                    clock.zoom = x;
+                    clock.zoom_update = true;
+                }
             });
         }
 
@@ -2292,7 +4743,8 @@ fn make_zoom_menu(app_info: &Rc<RefCell<AppInfo>>) -> Menu
     menu
 }
 
-fn make_timezone_menu(da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>) -> Menu
+// the time zone of the clock, or the second time zone
+fn make_timezone_menu(da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>, clock_id: u32, second: bool) -> Menu
 {
     // first parse
 
@@ -2322,14 +4774,19 @@ fn make_timezone_menu(da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>) -> Menu
 
     let menu = Menu::new();
 
-    let tz = app_info.borrow().time_zone.clone();
-    let tz = if tz == ""
+    let time_zone = app_info
+        .borrow()
+        .clock(clock_id)
+        .map(|x| if second { x.time_zone2.clone() } else { x.time_zone.clone() })
+        .unwrap_or_default();
+
+    let tz = if time_zone == ""
     {
         String::from("<< (Local Time) >>")
     }
     else
     {
-        format!("<< {} >>", tz)
+        format!("<< {} >>", time_zone)
     };
 
     let menu_item_now = MenuItem::with_label(tz.as_str());
@@ -2340,16 +4797,18 @@ fn make_timezone_menu(da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>) -> Menu
 
     let menu_item_local_time = CheckMenuItem::with_label("Local Time");
 
-    menu_item_local_time.set_active(app_info.borrow().time_zone == "");
+    menu_item_local_time.set_active(time_zone == "");
 
     {
         let da = da.clone();
         let app_info = app_info.clone();
 
         menu_item_local_time.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.time_zone = String::from("");
-            app_info.time_disp_st = None;
+            if let Some(clock) = app_info.borrow_mut().clock_mut(clock_id)
+            {
+                *clock.time_zone_mut(second) = String::from("");
+                clock.time_disp_st = None;
+            }
             da.queue_draw();
         });
     }
@@ -2358,15 +4817,17 @@ fn make_timezone_menu(da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>) -> Menu
 
     let menu_item_utc = CheckMenuItem::with_label("UTC");
 
-    menu_item_utc.set_active(app_info.borrow().time_zone == "UTC");
+    menu_item_utc.set_active(time_zone == "UTC");
 
     {
         let da = da.clone();
         let app_info = app_info.clone();
 
         menu_item_utc.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.time_zone = String::from("UTC");
+            if let Some(clock) = app_info.borrow_mut().clock_mut(clock_id)
+            {
+                *clock.time_zone_mut(second) = String::from("UTC");
+            }
             da.queue_draw();
         });
     }
@@ -2385,15 +4846,17 @@ fn make_timezone_menu(da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>) -> Menu
     {
         let menu_item_gmt_entry = CheckMenuItem::with_label(gmt_entry);
 
-        menu_item_gmt_entry.set_active(app_info.borrow().time_zone == gmt_entry);
+        menu_item_gmt_entry.set_active(time_zone == gmt_entry);
 
         {
             let da = da.clone();
             let app_info = app_info.clone();
 
             menu_item_gmt_entry.connect_activate(move |_| {
-                let mut app_info = app_info.borrow_mut();
-                app_info.time_zone = String::from(gmt_entry);
+                if let Some(clock) = app_info.borrow_mut().clock_mut(clock_id)
+                {
+                    *clock.time_zone_mut(second) = String::from(gmt_entry);
+                }
                 da.queue_draw();
             });
         }
@@ -2419,14 +4882,16 @@ fn make_timezone_menu(da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>) -> Menu
 
                 let menu_item_city = CheckMenuItem::with_label(city);
 
-                menu_item_city.set_active(app_info.borrow().time_zone == tz);
+                menu_item_city.set_active(time_zone == tz);
 
                 {
                     let da = da.clone();
                     let app_info = app_info.clone();
                     menu_item_city.connect_activate(move |_| {
-                        let mut app_info = app_info.borrow_mut();
-                        app_info.time_zone = tz.clone();
+                        if let Some(clock) = app_info.borrow_mut().clock_mut(clock_id)
+                        {
+                            *clock.time_zone_mut(second) = tz.clone();
+                        }
                         da.queue_draw();
                     });
                 }
@@ -2485,6 +4950,15 @@ fn make_text_visibility_menu(
     {
         false
     };
+    let with_digit = if let Some(x) = image_info.borrow().config.with_text_digit
+        && x
+    {
+        true
+    }
+    else
+    {
+        false
+    };
 
     let menu = Menu::new();
 
@@ -2497,171 +4971,592 @@ fn make_text_visibility_menu(
         let da = da.clone();
         let app_info = app_info.clone();
 
-        menu_item_enable_text_time_zone.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.enable_text_time_zone = !app_info.enable_text_time_zone;
-            da.queue_draw();
-        });
+        menu_item_enable_text_time_zone.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.enable_text_time_zone = !app_info.enable_text_time_zone;
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_enable_text_date = CheckMenuItem::with_label("Enable Date");
+
+    menu_item_enable_text_date.set_sensitive(with_date);
+    menu_item_enable_text_date.set_active(app_info.borrow().enable_text_date);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_enable_text_date.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.enable_text_date = !app_info.enable_text_date;
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_enable_text_time = CheckMenuItem::with_label("Enable Time");
+
+    menu_item_enable_text_time.set_sensitive(with_time);
+    menu_item_enable_text_time.set_active(app_info.borrow().enable_text_time);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_enable_text_time.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.enable_text_time = !app_info.enable_text_time;
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_enable_text_time_segment = CheckMenuItem::with_label("Enable Time Segment");
+
+    menu_item_enable_text_time_segment.set_sensitive(with_segment);
+    menu_item_enable_text_time_segment.set_active(app_info.borrow().enable_text_time_segment);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_enable_text_time_segment.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.enable_text_time_segment = !app_info.enable_text_time_segment;
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_enable_text_time_segment_hour12 =
+        CheckMenuItem::with_label("Enable Time Segment Hour 12");
+
+    menu_item_enable_text_time_segment_hour12.set_sensitive(with_segment || with_digit);
+    menu_item_enable_text_time_segment_hour12
+        .set_active(app_info.borrow().enable_text_time_segment_hour12);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_enable_text_time_segment_hour12.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.enable_text_time_segment_hour12 = !app_info.enable_text_time_segment_hour12;
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_enable_text_time_segment_dotblink =
+        CheckMenuItem::with_label("Enable Time Segment dot blink");
+
+    menu_item_enable_text_time_segment_dotblink
+        .set_active(app_info.borrow().enable_text_time_segment_dotblink);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_enable_text_time_segment_dotblink.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.enable_text_time_segment_dotblink =
+                !app_info.enable_text_time_segment_dotblink;
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_text_format_date = MenuItem::with_label("Formant Date");
+
+    let menu_text_format_date = Menu::new();
+
+    for x in AppInfoFormatDate::iter()
+    {
+        let menu_item = CheckMenuItem::with_label(x.format_str().1);
+
+        menu_item.set_active(app_info.borrow().text_format_date == x);
+
+        {
+            let da = da.clone();
+            let app_info = app_info.clone();
+
+            menu_item.connect_activate(move |_| {
+                let mut app_info = app_info.borrow_mut();
+                app_info.text_format_date = x;
+                da.queue_draw();
+            });
+        }
+
+        menu_text_format_date.append(&menu_item);
+    }
+
+    menu_item_text_format_date.set_submenu(Some(&menu_text_format_date));
+
+    let menu_item_text_format_time = MenuItem::with_label("Formant Time");
+
+    let menu_text_format_time = Menu::new();
+
+    for x in AppInfoFormatTime::iter()
+    {
+        let menu_item = CheckMenuItem::with_label(x.format_str().1);
+
+        menu_item.set_active(app_info.borrow().text_format_time == x);
+
+        {
+            let da = da.clone();
+            let app_info = app_info.clone();
+
+            menu_item.connect_activate(move |_| {
+                let mut app_info = app_info.borrow_mut();
+                app_info.text_format_time = x;
+                da.queue_draw();
+            });
+        }
+
+        menu_text_format_time.append(&menu_item);
+    }
+
+    menu_item_text_format_time.set_submenu(Some(&menu_text_format_time));
+
+    menu.append(&menu_item_enable_text_time_zone);
+    menu.append(&menu_item_enable_text_date);
+    menu.append(&menu_item_enable_text_time);
+    menu.append(&menu_item_enable_text_time_segment);
+    menu.append(&menu_item_enable_text_time_segment_hour12);
+    menu.append(&SeparatorMenuItem::new());
+    menu.append(&menu_item_text_format_date);
+    menu.append(&menu_item_text_format_time);
+
+    menu
+}
+
+fn show_location_dialog(window: &ApplicationWindow, da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>)
+{
+    // latitude / longitude for sunrise, sunset and the daylight arc. empty = not set
+
+    let dialog = Dialog::with_buttons(
+        Some("Location"),
+        Some(window),
+        DialogFlags::MODAL | DialogFlags::DESTROY_WITH_PARENT,
+        &[("Cancel", ResponseType::Cancel), ("OK", ResponseType::Ok)],
+    );
+
+    let grid = Grid::new();
+
+    grid.set_row_spacing(6);
+    grid.set_column_spacing(12);
+    grid.set_border_width(12);
+
+    let entry_latitude = Entry::new();
+    let entry_longitude = Entry::new();
+
+    entry_latitude.set_placeholder_text(Some("35.6812 ( north + / south - )"));
+    entry_longitude.set_placeholder_text(Some("139.7671 ( east + / west - )"));
+
+    if let Some(x) = app_info.borrow().latitude
+    {
+        entry_latitude.set_text(&x.to_string());
+    }
+
+    if let Some(x) = app_info.borrow().longitude
+    {
+        entry_longitude.set_text(&x.to_string());
+    }
+
+    grid.attach(&Label::new(Some("Latitude")), 0, 0, 1, 1);
+    grid.attach(&entry_latitude, 1, 0, 1, 1);
+    grid.attach(&Label::new(Some("Longitude")), 0, 1, 1, 1);
+    grid.attach(&entry_longitude, 1, 1, 1, 1);
+
+    dialog.content_area().add(&grid);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        dialog.connect_response(move |dialog, response| {
+            if response == ResponseType::Ok
+            {
+                let fn_parse = |entry: &Entry, limit: f64| {
+                    f64::from_str(entry.text().trim()).ok().filter(|x| x.abs() <= limit)
+                };
+
+                let mut app_info = app_info.borrow_mut();
+
+                app_info.latitude = fn_parse(&entry_latitude, 90.0);
+                app_info.longitude = fn_parse(&entry_longitude, 180.0);
+
+                da.queue_draw();
+            }
+
+            dialog.close();
+        });
+    }
+
+    dialog.show_all();
+}
+
+fn show_countdown_dialog(window: &ApplicationWindow, da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>)
+{
+    let dialog = Dialog::with_buttons(
+        Some("Timer"),
+        Some(window),
+        DialogFlags::MODAL | DialogFlags::DESTROY_WITH_PARENT,
+        &[("Cancel", ResponseType::Cancel), ("Start", ResponseType::Ok)],
+    );
+
+    let grid = Grid::new();
+
+    grid.set_row_spacing(6);
+    grid.set_column_spacing(12);
+    grid.set_border_width(12);
+
+    let entry_duration = Entry::new();
+
+    entry_duration.set_placeholder_text(Some("25 ( minutes ), 4:30, 1:30:00, 1h30m"));
+    entry_duration.set_activates_default(true);
+
+    let duration = app_info.borrow().countdown.duration();
+
+    if !duration.is_zero()
+    {
+        entry_duration.set_text(&format_remaining(duration));
     }
 
-    let menu_item_enable_text_date = CheckMenuItem::with_label("Enable Date");
+    grid.attach(&Label::new(Some("Duration")), 0, 0, 1, 1);
+    grid.attach(&entry_duration, 1, 0, 1, 1);
 
-    menu_item_enable_text_date.set_sensitive(with_date);
-    menu_item_enable_text_date.set_active(app_info.borrow().enable_text_date);
+    dialog.content_area().add(&grid);
+    dialog.set_default_response(ResponseType::Ok);
 
     {
         let da = da.clone();
         let app_info = app_info.clone();
 
-        menu_item_enable_text_date.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.enable_text_date = !app_info.enable_text_date;
-            da.queue_draw();
+        dialog.connect_response(move |dialog, response| {
+            if response == ResponseType::Ok
+            {
+                match parse_duration(&entry_duration.text())
+                {
+                    Some(x) =>
+                    {
+                        let mut app_info = app_info.borrow_mut();
+                        app_info.countdown.start(&Utc::now(), x);
+                        app_info.countdown_flash = None;
+                        da.queue_draw();
+                    },
+                    None =>
+                    {
+                        // keep the dialog open
+                        entry_duration.grab_focus();
+                        return;
+                    },
+                }
+            }
+
+            dialog.close();
         });
     }
 
-    let menu_item_enable_text_time = CheckMenuItem::with_label("Enable Time");
+    dialog.show_all();
+}
 
-    menu_item_enable_text_time.set_sensitive(with_time);
-    menu_item_enable_text_time.set_active(app_info.borrow().enable_text_time);
+struct AlarmRow
+{
+    id:       u32, // 0 = a new alarm
+    enabled:  CheckButton,
+    time:     Entry,
+    weekdays: Vec<CheckButton>,
+    label:    Entry,
+    remove:   Button,
+}
 
+impl AlarmRow
+{
+    fn widgets(&self) -> Vec<gtk::Widget>
     {
-        let da = da.clone();
-        let app_info = app_info.clone();
+        let mut ret: Vec<gtk::Widget> = vec![self.enabled.clone().upcast(), self.time.clone().upcast()];
 
-        menu_item_enable_text_time.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.enable_text_time = !app_info.enable_text_time;
-            da.queue_draw();
-        });
+        ret.extend(self.weekdays.iter().map(|x| x.clone().upcast()));
+        ret.push(self.label.clone().upcast());
+        ret.push(self.remove.clone().upcast());
+
+        ret
     }
+}
 
-    let menu_item_enable_text_time_segment = CheckMenuItem::with_label("Enable Time Segment");
+fn show_alarm_dialog(window: &ApplicationWindow, da: &DrawingArea, app_info: &Rc<RefCell<AppInfo>>)
+{
+    /*
+        On  Time   Sun Mon Tue Wed Thu Fri Sat  Label
+        [x] 07:30  [ ] [x] [x] [x] [x] [x] [ ]  Wake up   Remove
+
+        no weekday = every day
+    */
+
+    let dialog = Dialog::with_buttons(
+        Some("Alarms"),
+        Some(window),
+        DialogFlags::MODAL | DialogFlags::DESTROY_WITH_PARENT,
+        &[("Cancel", ResponseType::Cancel), ("OK", ResponseType::Ok)],
+    );
 
-    menu_item_enable_text_time_segment.set_sensitive(with_segment);
-    menu_item_enable_text_time_segment.set_active(app_info.borrow().enable_text_time_segment);
+    let grid = Grid::new();
 
-    {
-        let da = da.clone();
-        let app_info = app_info.clone();
+    grid.set_row_spacing(6);
+    grid.set_column_spacing(6);
+    grid.set_border_width(12);
 
-        menu_item_enable_text_time_segment.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.enable_text_time_segment = !app_info.enable_text_time_segment;
-            da.queue_draw();
-        });
+    grid.attach(&Label::new(Some("On")), 0, 0, 1, 1);
+    grid.attach(&Label::new(Some("Time")), 1, 0, 1, 1);
+
+    for (i, day) in AlarmDay::ALL.iter().enumerate()
+    {
+        grid.attach(&Label::new(Some(day.name())), 2 + i as i32, 0, 1, 1);
     }
 
-    let menu_item_enable_text_time_segment_hour12 =
-        CheckMenuItem::with_label("Enable Time Segment Hour 12");
+    grid.attach(&Label::new(Some("Label")), 9, 0, 1, 1);
 
-    menu_item_enable_text_time_segment_hour12.set_sensitive(with_segment);
-    menu_item_enable_text_time_segment_hour12
-        .set_active(app_info.borrow().enable_text_time_segment_hour12);
+    let rows = Rc::new(RefCell::new(Vec::<AlarmRow>::new()));
+    let next_row = Rc::new(std::cell::Cell::new(1));
 
-    {
-        let da = da.clone();
-        let app_info = app_info.clone();
+    let fn_add_row = {
+        let grid = grid.clone();
+        let rows = rows.clone();
 
-        menu_item_enable_text_time_segment_hour12.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.enable_text_time_segment_hour12 = !app_info.enable_text_time_segment_hour12;
-            da.queue_draw();
-        });
+        move |alarm: &Alarm| {
+            let row = AlarmRow {
+                id:       alarm.id,
+                enabled:  CheckButton::new(),
+                time:     Entry::new(),
+                weekdays: AlarmDay::ALL.iter().map(|_| CheckButton::new()).collect(),
+                label:    Entry::new(),
+                remove:   Button::with_label("Remove"),
+            };
+
+            row.enabled.set_active(alarm.enabled);
+            row.time.set_text(&alarm.time.format("%H:%M").to_string());
+            row.time.set_width_chars(6);
+            row.time.set_placeholder_text(Some("07:30"));
+            row.label.set_text(&alarm.label);
+
+            for (day, check) in AlarmDay::ALL.iter().zip(row.weekdays.iter())
+            {
+                check.set_active(alarm.weekdays.contains(day));
+            }
+
+            let y = next_row.get();
+
+            next_row.set(y + 1);
+
+            for (x, widget) in row.widgets().iter().enumerate()
+            {
+                grid.attach(widget, x as i32, y, 1, 1);
+            }
+
+            {
+                let grid = grid.clone();
+                let rows = rows.clone();
+
+                row.remove.connect_clicked(move |button| {
+                    let mut rows = rows.borrow_mut();
+
+                    if let Some(pos) = rows.iter().position(|x| x.remove == *button)
+                    {
+                        for widget in rows.remove(pos).widgets()
+                        {
+                            grid.remove(&widget);
+                        }
+                    }
+                });
+            }
+
+            grid.show_all();
+
+            rows.borrow_mut().push(row);
+        }
+    };
+
+    for alarm in app_info.borrow().alarms.iter()
+    {
+        fn_add_row(alarm);
     }
 
-    let menu_item_enable_text_time_segment_dotblink =
-        CheckMenuItem::with_label("Enable Time Segment dot blink");
+    let button_add = Button::with_label("Add Alarm");
 
-    menu_item_enable_text_time_segment_dotblink
-        .set_active(app_info.borrow().enable_text_time_segment_dotblink);
+    button_add.connect_clicked(move |_| {
+        fn_add_row(&Alarm::new(NaiveTime::from_hms_opt(7, 0, 0).unwrap()));
+    });
+
+    dialog.content_area().add(&grid);
+    dialog.content_area().add(&button_add);
 
     {
         let da = da.clone();
         let app_info = app_info.clone();
 
-        menu_item_enable_text_time_segment_dotblink.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.enable_text_time_segment_dotblink =
-                !app_info.enable_text_time_segment_dotblink;
-            da.queue_draw();
+        dialog.connect_response(move |dialog, response| {
+            if response == ResponseType::Ok
+            {
+                let mut alarms = Vec::<Alarm>::new();
+
+                for row in rows.borrow().iter()
+                {
+                    let Some(time) = parse_alarm_time(&row.time.text())
+                    else
+                    {
+                        // keep the dialog open
+                        row.time.grab_focus();
+                        return;
+                    };
+
+                    let mut alarm = Alarm::new(time);
+
+                    alarm.id = row.id;
+                    alarm.enabled = row.enabled.is_active();
+                    alarm.label = row.label.text().trim().to_string();
+                    alarm.weekdays = AlarmDay::ALL
+                        .iter()
+                        .zip(row.weekdays.iter())
+                        .filter(|(_, check)| check.is_active())
+                        .map(|(day, _)| *day)
+                        .collect();
+
+                    alarms.push(alarm);
+                }
+
+                let mut app_info = app_info.borrow_mut();
+
+                // the snoozes of the removed alarms are dropped by fire_alarms
+                app_info.alarm_id_next = assign_alarm_ids(&mut alarms, app_info.alarm_id_next);
+                app_info.alarms = alarms;
+
+                da.queue_draw();
+            }
+
+            dialog.close();
         });
     }
 
-    let menu_item_text_format_date = MenuItem::with_label("Formant Date");
+    dialog.show_all();
+}
 
-    let menu_text_format_date = Menu::new();
+fn show_chime_dialog(window: &ApplicationWindow, app_info: &Rc<RefCell<AppInfo>>)
+{
+    let dialog = Dialog::with_buttons(
+        Some("Chimes"),
+        Some(window),
+        DialogFlags::MODAL | DialogFlags::DESTROY_WITH_PARENT,
+        &[("Cancel", ResponseType::Cancel), ("OK", ResponseType::Ok)],
+    );
+
+    let grid = Grid::new();
+
+    grid.set_row_spacing(6);
+    grid.set_column_spacing(12);
+    grid.set_border_width(12);
+
+    let check_hour = CheckButton::with_label("Hour");
+    let check_half_hour = CheckButton::with_label("Half hour");
+    let check_quarter_hour = CheckButton::with_label("Quarter hours");
+    let check_notify = CheckButton::with_label("Notification");
+    let entry_quiet_start = Entry::new();
+    let entry_quiet_end = Entry::new();
+    let entry_command = Entry::new();
 
-    for x in AppInfoFormatDate::iter()
     {
-        let menu_item = CheckMenuItem::with_label(x.format_str().1);
+        let app_info = app_info.borrow();
+        let chime = &app_info.chime;
 
-        menu_item.set_active(app_info.borrow().text_format_date == x);
+        check_hour.set_active(chime.hour);
+        check_half_hour.set_active(chime.half_hour);
+        check_quarter_hour.set_active(chime.quarter_hour);
+        check_notify.set_active(chime.notify);
 
+        for (entry, time) in [(&entry_quiet_start, chime.quiet_start), (&entry_quiet_end, chime.quiet_end)]
         {
-            let da = da.clone();
-            let app_info = app_info.clone();
-
-            menu_item.connect_activate(move |_| {
-                let mut app_info = app_info.borrow_mut();
-                app_info.text_format_date = x;
-                da.queue_draw();
-            });
+            if let Some(x) = time
+            {
+                entry.set_text(&x.format("%H:%M").to_string());
+            }
         }
 
-        menu_text_format_date.append(&menu_item);
+        if let Some(x) = chime.command.as_ref()
+        {
+            entry_command.set_text(x);
+        }
     }
 
-    menu_item_text_format_date.set_submenu(Some(&menu_text_format_date));
+    entry_quiet_start.set_placeholder_text(Some("22:00"));
+    entry_quiet_end.set_placeholder_text(Some("07:00"));
+    entry_command.set_placeholder_text(Some("paplay bell.wav ( {hour12} = the number of strikes )"));
+    entry_command.set_width_chars(40);
 
-    let menu_item_text_format_time = MenuItem::with_label("Formant Time");
+    grid.attach(&check_hour, 0, 0, 1, 1);
+    grid.attach(&check_half_hour, 1, 0, 1, 1);
+    grid.attach(&check_quarter_hour, 2, 0, 1, 1);
+    grid.attach(&Label::new(Some("Quiet from")), 0, 1, 1, 1);
+    grid.attach(&entry_quiet_start, 1, 1, 1, 1);
+    grid.attach(&Label::new(Some("Quiet to")), 0, 2, 1, 1);
+    grid.attach(&entry_quiet_end, 1, 2, 1, 1);
+    grid.attach(&Label::new(Some("Command")), 0, 3, 1, 1);
+    grid.attach(&entry_command, 1, 3, 2, 1);
+    grid.attach(&check_notify, 0, 4, 1, 1);
 
-    let menu_text_format_time = Menu::new();
+    dialog.content_area().add(&grid);
 
-    for x in AppInfoFormatTime::iter()
     {
-        let menu_item = CheckMenuItem::with_label(x.format_str().1);
-
-        menu_item.set_active(app_info.borrow().text_format_time == x);
+        let app_info = app_info.clone();
 
-        {
-            let da = da.clone();
-            let app_info = app_info.clone();
+        dialog.connect_response(move |dialog, response| {
+            if response == ResponseType::Ok
+            {
+                let mut quiet = [None, None];
 
-            menu_item.connect_activate(move |_| {
-                let mut app_info = app_info.borrow_mut();
-                app_info.text_format_time = x;
-                da.queue_draw();
-            });
-        }
+                for (i, entry) in [&entry_quiet_start, &entry_quiet_end].iter().enumerate()
+                {
+                    if entry.text().trim() != ""
+                    {
+                        match parse_quiet_time(&entry.text())
+                        {
+                            Some(x) => quiet[i] = Some(x),
+                            None =>
+                            {
+                                // keep the dialog open
+                                entry.grab_focus();
+                                return;
+                            },
+                        }
+                    }
+                }
 
-        menu_text_format_time.append(&menu_item);
-    }
+                let command = entry_command.text().trim().to_string();
 
-    menu_item_text_format_time.set_submenu(Some(&menu_text_format_time));
+                app_info.borrow_mut().chime = ChimeConfig {
+                    hour: check_hour.is_active(),
+                    half_hour: check_half_hour.is_active(),
+                    quarter_hour: check_quarter_hour.is_active(),
+                    quiet_start: quiet[0],
+                    quiet_end: quiet[1],
+                    command: if command == "" { None } else { Some(command) },
+                    notify: check_notify.is_active(),
+                };
+            }
 
-    menu.append(&menu_item_enable_text_time_zone);
-    menu.append(&menu_item_enable_text_date);
-    menu.append(&menu_item_enable_text_time);
-    menu.append(&menu_item_enable_text_time_segment);
-    menu.append(&menu_item_enable_text_time_segment_hour12);
-    menu.append(&SeparatorMenuItem::new());
-    menu.append(&menu_item_text_format_date);
-    menu.append(&menu_item_text_format_time);
+            dialog.close();
+        });
+    }
 
-    menu
+    dialog.show_all();
 }
 
 fn make_popup_menu(
     window: &ApplicationWindow,
     da: &DrawingArea,
     app_info: &Rc<RefCell<AppInfo>>,
+    clock_id: u32,
     image_info: &Rc<RefCell<ImageInfo>>,
     logo: Option<Pixbuf>,
 ) -> Menu
 {
     let with_second_handle = image_info.borrow().svgh_second_handle.is_some();
-    let with_sub_second_handle = image_info.borrow().svgh_sub_second_handle.is_some();
+    let with_sub_second_handle = image_info.borrow().sub_dials.iter().any(|x| x.is_second());
 
     let menu = Menu::new();
 
@@ -2679,73 +5574,135 @@ fn make_popup_menu(
         menu_item_pref_alway_on_top.connect_activate(move |_| {
             let mut app_info = app_info.borrow_mut();
             app_info.always_on_top = !app_info.always_on_top;
-            window.set_keep_above(app_info.always_on_top);
+
+            if let Some(app) = window.application()
+            {
+                for x in app.windows()
+                {
+                    x.set_keep_above(app_info.always_on_top);
+                }
+            }
+
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_pref_lock_pos = CheckMenuItem::with_label("Lock Position");
+
+    menu_item_pref_lock_pos.set_active(app_info.borrow().lock_pos);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_pref_lock_pos.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.lock_pos = !app_info.lock_pos;
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_pref_show_seconds = CheckMenuItem::with_label("Show Seconds");
+
+    menu_item_pref_show_seconds.set_sensitive(with_second_handle);
+    menu_item_pref_show_seconds.set_active(app_info.borrow().show_seconds);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_pref_show_seconds.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.show_seconds = !app_info.show_seconds;
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_pref_enable_sub_second_handle =
+        CheckMenuItem::with_label("Enable sub second handle");
+
+    menu_item_pref_enable_sub_second_handle.set_sensitive(with_sub_second_handle);
+    menu_item_pref_enable_sub_second_handle.set_active(app_info.borrow().enable_sub_second_handle);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_pref_enable_sub_second_handle.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.enable_sub_second_handle = !app_info.enable_sub_second_handle;
+            da.queue_draw();
+        });
+    }
+
+    let menu_item_pref_enable_second_handle_smoothly =
+        CheckMenuItem::with_label("Enable second handle smoothly");
+
+    menu_item_pref_enable_second_handle_smoothly
+        .set_active(app_info.borrow().enable_second_handle_smoothly);
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        menu_item_pref_enable_second_handle_smoothly.connect_activate(move |_| {
+            let mut app_info = app_info.borrow_mut();
+            app_info.enable_second_handle_smoothly = !app_info.enable_second_handle_smoothly;
             da.queue_draw();
         });
     }
 
-    let menu_item_pref_lock_pos = CheckMenuItem::with_label("Lock Position");
+    let with_daylight_arc = image_info.borrow().config.daylight_arc.is_some();
+
+    let menu_item_pref_enable_daylight_arc = CheckMenuItem::with_label("Show Daylight Arc");
 
-    menu_item_pref_lock_pos.set_active(app_info.borrow().lock_pos);
+    menu_item_pref_enable_daylight_arc
+        .set_sensitive(with_daylight_arc && app_info.borrow().location().is_some());
+    menu_item_pref_enable_daylight_arc.set_active(app_info.borrow().enable_daylight_arc);
 
     {
         let da = da.clone();
         let app_info = app_info.clone();
 
-        menu_item_pref_lock_pos.connect_activate(move |_| {
+        menu_item_pref_enable_daylight_arc.connect_activate(move |_| {
             let mut app_info = app_info.borrow_mut();
-            app_info.lock_pos = !app_info.lock_pos;
+            app_info.enable_daylight_arc = !app_info.enable_daylight_arc;
             da.queue_draw();
         });
     }
 
-    let menu_item_pref_show_seconds = CheckMenuItem::with_label("Show Seconds");
-
-    menu_item_pref_show_seconds.set_sensitive(with_second_handle);
-    menu_item_pref_show_seconds.set_active(app_info.borrow().show_seconds);
+    let menu_item_pref_location = MenuItem::with_label("Location…");
 
     {
+        let window = window.clone();
         let da = da.clone();
         let app_info = app_info.clone();
 
-        menu_item_pref_show_seconds.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.show_seconds = !app_info.show_seconds;
-            da.queue_draw();
+        menu_item_pref_location.connect_activate(move |_| {
+            show_location_dialog(&window, &da, &app_info);
         });
     }
 
-    let menu_item_pref_enable_sub_second_handle =
-        CheckMenuItem::with_label("Enable sub second handle");
-
-    menu_item_pref_enable_sub_second_handle.set_sensitive(with_sub_second_handle);
-    menu_item_pref_enable_sub_second_handle.set_active(app_info.borrow().enable_sub_second_handle);
+    let menu_item_pref_alarms = MenuItem::with_label("Alarms…");
 
     {
+        let window = window.clone();
         let da = da.clone();
         let app_info = app_info.clone();
 
-        menu_item_pref_enable_sub_second_handle.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.enable_sub_second_handle = !app_info.enable_sub_second_handle;
-            da.queue_draw();
+        menu_item_pref_alarms.connect_activate(move |_| {
+            show_alarm_dialog(&window, &da, &app_info);
         });
     }
 
-    let menu_item_pref_enable_second_handle_smoothly =
-        CheckMenuItem::with_label("Enable second handle smoothly");
-
-    menu_item_pref_enable_second_handle_smoothly
-        .set_active(app_info.borrow().enable_second_handle_smoothly);
+    let menu_item_pref_chimes = MenuItem::with_label("Chimes…");
 
     {
-        let da = da.clone();
+        let window = window.clone();
         let app_info = app_info.clone();
 
-        menu_item_pref_enable_second_handle_smoothly.connect_activate(move |_| {
-            let mut app_info = app_info.borrow_mut();
-            app_info.enable_second_handle_smoothly = !app_info.enable_second_handle_smoothly;
-            da.queue_draw();
+        menu_item_pref_chimes.connect_activate(move |_| {
+            show_chime_dialog(&window, &app_info);
         });
     }
 
@@ -2756,6 +5713,7 @@ fn make_popup_menu(
     menu_item_pref_text_visibility.set_submenu(Some(&menu_pref_text_visibility));
 
     let menu_item_pref_time_zone = MenuItem::with_label("Time Zone");
+    let menu_item_pref_time_zone2 = MenuItem::with_label("Second Time Zone");
     let menu_item_pref_theme = MenuItem::with_label("Theme");
     let menu_item_pref_zoom = MenuItem::with_label("Zoom");
 
@@ -2767,24 +5725,81 @@ fn make_popup_menu(
     menu_pref.append(&menu_item_pref_show_seconds);
     menu_pref.append(&menu_item_pref_enable_sub_second_handle);
     menu_pref.append(&menu_item_pref_enable_second_handle_smoothly);
+    menu_pref.append(&menu_item_pref_enable_daylight_arc);
     menu_pref.append(&SeparatorMenuItem::new());
     menu_pref.append(&menu_item_pref_text_visibility);
     menu_pref.append(&SeparatorMenuItem::new());
     menu_pref.append(&menu_item_pref_time_zone);
+    menu_pref.append(&menu_item_pref_time_zone2);
+    menu_pref.append(&menu_item_pref_location);
+    menu_pref.append(&menu_item_pref_alarms);
+    menu_pref.append(&menu_item_pref_chimes);
     menu_pref.append(&menu_item_pref_theme);
     menu_pref.append(&menu_item_pref_zoom);
 
     menu_item_pref.set_submenu(Some(&menu_pref));
 
-    let menu_pref_time_zone = make_timezone_menu(&da.clone(), &app_info.clone());
+    let menu_pref_time_zone = make_timezone_menu(&da.clone(), &app_info.clone(), clock_id, false);
     menu_item_pref_time_zone.set_submenu(Some(&menu_pref_time_zone));
 
-    let menu_pref_theme = make_theme_menu(&image_info.clone(), &app_info.clone());
+    let menu_pref_time_zone2 = make_timezone_menu(&da.clone(), &app_info.clone(), clock_id, true);
+    menu_item_pref_time_zone2.set_submenu(Some(&menu_pref_time_zone2));
+
+    let menu_pref_theme = make_theme_menu(&window.clone(), &image_info.clone(), &app_info.clone(), clock_id);
     menu_item_pref_theme.set_submenu(Some(&menu_pref_theme));
 
-    let menu_pref_zoom = make_zoom_menu(&app_info.clone());
+    let menu_pref_zoom = make_zoom_menu(&app_info.clone(), clock_id);
     menu_item_pref_zoom.set_submenu(Some(&menu_pref_zoom));
 
+    let menu_item_countdown = MenuItem::with_label("Timer");
+
+    let menu_countdown = make_countdown_menu(&window.clone(), &da.clone(), &app_info.clone());
+    menu_item_countdown.set_submenu(Some(&menu_countdown));
+
+    let menu_item_stopwatch = MenuItem::with_label("Stopwatch");
+
+    let menu_stopwatch = make_stopwatch_menu(&da.clone(), &app_info.clone());
+    menu_item_stopwatch.set_submenu(Some(&menu_stopwatch));
+
+    let menu_item_clock_new = MenuItem::with_label("New clock");
+
+    {
+        let window = window.clone();
+        let app_info = app_info.clone();
+
+        menu_item_clock_new.connect_activate(move |_| {
+            if let Some(app) = window.application()
+            {
+                // beside this clock
+                let (x, y) = window.position();
+                let (w, _) = window.size();
+
+                let id = app_info.borrow_mut().add_clock(clock_id, Some((x + w, y)));
+
+                build_clock_window(&app, &app_info, id);
+            }
+        });
+    }
+
+    let menu_item_clock_close = MenuItem::with_label("Close this clock");
+
+    menu_item_clock_close.set_sensitive(app_info.borrow().clock_ids().len() > 1);
+
+    {
+        let window = window.clone();
+        let app_info = app_info.clone();
+
+        menu_item_clock_close.connect_activate(move |_| {
+            let clock = app_info.borrow_mut().remove_clock(clock_id);
+
+            if let Some(mut clock) = clock
+            {
+                clock.close();
+                window.close();
+            }
+        });
+    }
+
     let menu_item_about = MenuItem::with_label("About");
 
     {
@@ -2817,11 +5832,20 @@ fn make_popup_menu(
         let window = window.clone();
 
         menu_item_quit.connect_activate(move |_| {
-            window.close();
+            // all clocks
+            if let Some(app) = window.application()
+            {
+                app.quit();
+            }
         });
     }
 
     menu.append(&menu_item_pref);
+    menu.append(&menu_item_countdown);
+    menu.append(&menu_item_stopwatch);
+    menu.append(&SeparatorMenuItem::new());
+    menu.append(&menu_item_clock_new);
+    menu.append(&menu_item_clock_close);
     menu.append(&SeparatorMenuItem::new());
 
     if app_info.borrow().time_disp_force.is_some()
@@ -2833,7 +5857,12 @@ fn make_popup_menu(
             let image_info = image_info.clone();
 
             menu_item_snapshot.connect_activate(move |_| {
-                make_png_image(&image_info.borrow(), &mut app_info.borrow_mut());
+                let app_info = app_info.borrow();
+
+                if let Some(clock) = app_info.clock(clock_id)
+                {
+                    make_png_image(&image_info.borrow(), &app_info, clock);
+                }
             });
         }
 
@@ -2858,10 +5887,247 @@ fn get_timer_interval(is_fast: bool) -> u64
         UPDATE_CYCLE_SLOW
     }
 }
+fn build_clock_window(app: &Application, app_info: &Rc<RefCell<AppInfo>>, clock_id: u32)
+{
+    let result = {
+        let app_info = app_info.borrow();
+
+        match app_info.clock(clock_id)
+        {
+            Some(clock) => app_info.load_theme(&clock.theme),
+            None => return,
+        }
+    };
+
+    let image_info = match result
+    {
+        Ok(x) => Rc::new(RefCell::new(x)),
+        Err(err) =>
+        {
+            let mut app_info = app_info.borrow_mut();
+
+            if let Some(clock) = app_info.clock_mut(clock_id)
+            {
+                error!("theme {} : {}", clock.theme, err);
+
+                clock.theme = String::from(THEME_ID_DEFAULT);
+                clock.theme_error = Some(err.to_string());
+            }
+
+            match app_info.load_theme(THEME_ID_DEFAULT)
+            {
+                Ok(x) => Rc::new(RefCell::new(x)),
+                Err(err) =>
+                {
+                    // no theme to draw. the clock shows the error only
+
+                    error!("theme {} : {}", THEME_ID_DEFAULT, err);
+
+                    if let Some(clock) = app_info.clock_mut(clock_id)
+                    {
+                        clock.theme_error = Some(err.to_string());
+                    }
+
+                    Rc::new(RefCell::new(ImageInfo::new_for_error()))
+                },
+            }
+        },
+    };
+
+    watch_theme(&image_info, app_info, clock_id);
+
+    let window = ApplicationWindow::builder()
+        .application(app)
+        .title(GTK_APPLICATION_TITLE)
+        .decorated(false)
+        .tooltip_markup(GTK_APPLICATION_TOOLTIP)
+        .build();
+
+    window.set_keep_above(app_info.borrow().always_on_top);
+
+    if let Some(pos) = app_info.borrow().clock(clock_id).and_then(|x| x.window_pos)
+    {
+        window.move_(pos.0, pos.1);
+    }
+
+    let da = DrawingArea::new();
+
+    {
+        let da = da.clone();
+        let window = window.clone();
+        let image_info = image_info.clone();
+        let app_info = app_info.clone();
+
+        da.connect_draw(move |da, cr| {
+            update_watch(&da, &mut app_info.borrow_mut(), clock_id, image_info.borrow().digit_transition_positions());
+            update_region(&window, &image_info.borrow(), &mut app_info.borrow_mut(), clock_id);
+
+            let app_info = app_info.borrow();
+
+            if let Some(clock) = app_info.clock(clock_id)
+            {
+                draw_watch(cr, &image_info.borrow(), &app_info, clock, false);
+            }
+
+            gtk::glib::Propagation::Proceed
+        });
+    }
+
+    window.add(&da);
+
+    {
+        let window = window.clone();
+        let da = da.clone();
+        let image_info = image_info.clone();
+        let app_info = app_info.clone();
+
+        window.connect_button_press_event(move |window, evt| {
+            // log::debug!("pressed: {:?}", evt.button() );
+
+            let logo = load_logo();
+
+            // any click stops the flash of the expired timer
+            app_info.borrow_mut().countdown_flash = None;
+
+            match evt.button()
+            {
+                1 =>
+                /* left button */
+                {
+                    if !app_info.borrow().lock_pos
+                    {
+                        let btn = evt.as_ref();
+                        window.begin_move_drag(
+                            btn.button as i32,
+                            btn.x_root as i32,
+                            btn.y_root as i32,
+                            btn.time,
+                        );
+                    }
+                },
+                2 =>
+                /* middle button. the pushers of the stopwatch */
+                {
+                    let mut app_info = app_info.borrow_mut();
+                    let now = Instant::now();
+
+                    if !evt.state().contains(gtk::gdk::ModifierType::SHIFT_MASK)
+                    {
+                        // start / stop
+                        app_info.stopwatch.toggle(now);
+                    }
+                    else if app_info.stopwatch.is_running()
+                    {
+                        app_info.stopwatch.lap(now);
+                    }
+                    else
+                    {
+                        app_info.stopwatch.reset();
+                    }
+
+                    da.queue_draw();
+
+                    return gtk::glib::Propagation::Stop;
+                },
+                3 =>
+                /* right button */
+                {
+                    let menu = make_popup_menu(&window, &da, &app_info, clock_id, &image_info, logo);
+
+                    menu.show_all();
+                    menu.popup_at_pointer(Some(evt));
+
+                    return gtk::glib::Propagation::Stop;
+                },
+                _ =>
+                {},
+            }
+
+            gtk::glib::Propagation::Proceed
+        });
+    }
+
+    {
+        let app_info = app_info.clone();
+
+        // keep the position of every clock. the other windows get no delete event on quit
+        window.connect_configure_event(move |window, _| {
+            if let Some(clock) = app_info.borrow_mut().clock_mut(clock_id)
+            {
+                clock.window_pos = Some(window.position());
+            }
+            false
+        });
+    }
+
+    {
+        let app_info = app_info.clone();
+
+        window.connect_delete_event(move |window, _| {
+            log::debug!("connect_delete_event");
+            log::debug!("pos:{:?}", window.position());
+
+            if let Some(clock) = app_info.borrow_mut().clock_mut(clock_id)
+            {
+                clock.window_pos = Some(window.position());
+
+                // closing a window by the window manager quits all clocks
+                // ( the clock removed by "Close this clock" is not found here )
+                if let Some(app) = window.application()
+                {
+                    app.quit();
+                }
+            }
+
+            gtk::glib::Propagation::Proceed
+        });
+    }
+
+    window.show_all();
+
+    {
+        let da = da.clone();
+        let app_info = app_info.clone();
+
+        if let Some(clock) = app_info.borrow().clock(clock_id)
+        {
+            clock.timer_sourceid.replace(Some(gtk::glib::source::timeout_add_local(
+                std::time::Duration::from_millis(get_timer_interval(false)),
+                move || {
+                    da.queue_draw();
+                    gtk::glib::ControlFlow::Continue
+                },
+            )));
+        }
+    }
+}
+
 fn main()
 {
     pretty_env_logger::init();
 
+    {
+        let args: Vec<String> = std::env::args().collect();
+
+        if args.len() > 1 && args[1] == ARG_CHECK_THEME
+        {
+            if args.len() == 2
+            {
+                eprintln!("usage: {} {} <file.svg> ...", args[0], ARG_CHECK_THEME);
+                std::process::exit(2);
+            }
+
+            let mut ok = true;
+
+            for path in &args[2 ..]
+            {
+                ok &= check_theme(path);
+            }
+
+            std::process::exit(if ok { 0 } else { 1 });
+        }
+    }
+
     let app_info_file = get_app_info_file();
 
     let app = Application::builder()
@@ -2924,118 +6190,23 @@ fn main()
     {
         let app_info = app_info.clone();
 
-        app.connect_activate(move |app| {
-            let image_info = Rc::new(RefCell::new(
-                load_theme(
-                    app_info.borrow().theme,
-                    app_info.borrow().theme_custom.clone(),
-                )
-                .unwrap(),
-            ));
-
-            let window = ApplicationWindow::builder()
-                .application(app)
-                .title(GTK_APPLICATION_TITLE)
-                .decorated(false)
-                .tooltip_markup(GTK_APPLICATION_TOOLTIP)
-                .build();
-
-            window.set_keep_above(app_info.borrow().always_on_top);
-
-            if let Some(pos) = app_info.borrow().window_pos
-            {
-                window.move_(pos.0, pos.1);
-            }
-
-            let da = DrawingArea::new();
-
-            {
-                let da = da.clone();
-                let window = window.clone();
-                let image_info = image_info.clone();
-                let app_info = app_info.clone();
-
-                da.connect_draw(move |da, cr| {
-                    update_watch(&da, &mut app_info.borrow_mut());
-                    update_region(&window, &image_info.borrow(), &mut app_info.borrow_mut());
-                    draw_watch(cr, &image_info.borrow(), &app_info.borrow(), false);
-                    gtk::glib::Propagation::Proceed
-                });
-            }
-
-            window.add(&da);
-
-            {
-                let window = window.clone();
-                let da = da.clone();
-                let image_info = image_info.clone();
-                let app_info = app_info.clone();
-
-                window.connect_button_press_event(move |window, evt| {
-                    // log::debug!("pressed: {:?}", evt.button() );
-
-                    let logo = load_logo();
-
-                    match evt.button()
-                    {
-                        1 =>
-                        /* left button */
-                        {
-                            if !app_info.borrow().lock_pos
-                            {
-                                let btn = evt.as_ref();
-                                window.begin_move_drag(
-                                    btn.button as i32,
-                                    btn.x_root as i32,
-                                    btn.y_root as i32,
-                                    btn.time,
-                                );
-                            }
-                        },
-                        3 =>
-                        /* right button */
-                        {
-                            let menu = make_popup_menu(&window, &da, &app_info, &image_info, logo);
-
-                            menu.show_all();
-                            menu.popup_at_pointer(Some(evt));
-
-                            return gtk::glib::Propagation::Stop;
-                        },
-                        _ =>
-                        {},
-                    }
-
-                    gtk::glib::Propagation::Proceed
-                });
-            }
+        // once. not on every activation
+        app.connect_startup(move |app| {
+            start_event_timer(app, &app_info);
+        });
+    }
 
-            {
-                let app_info = app_info.clone();
+    {
+        let app_info = app_info.clone();
 
-                window.connect_delete_event(move |window, _| {
-                    log::debug!("connect_delete_event");
-                    log::debug!("pos:{:?}", window.position());
-                    app_info.borrow_mut().window_pos = Some(window.position());
-                    gtk::glib::Propagation::Proceed
-                });
-            }
+        app.connect_activate(move |app| {
+            add_alarm_actions(app, &app_info);
 
-            window.show_all();
+            let ids = app_info.borrow().clock_ids();
 
+            for id in ids
             {
-                let da = da.clone();
-                let app_info = app_info.clone();
-
-                app_info.borrow_mut().timer_sourceid.replace(Some(
-                    gtk::glib::source::timeout_add_local(
-                        std::time::Duration::from_millis(get_timer_interval(false)),
-                        move || {
-                            da.queue_draw();
-                            gtk::glib::ControlFlow::Continue
-                        },
-                    ),
-                ));
+                build_clock_window(app, &app_info, id);
             }
         });
     }
@@ -0,0 +1,278 @@
+use chrono::{Datelike, NaiveDateTime, Timelike};
+
+/*
+    The patterns of the segment displays.
+
+    seven segments. bit 6 .. 0 = a .. g
+    https://en.wikipedia.org/wiki/Seven-segment_display#/media/File:7_Segment_Display_with_Labeled_Segments.svg
+
+       =a=
+    |f|   |b|
+       =g=
+    |e|   |c|
+       =d=
+*/
+
+pub const SEVEN_SEGMENT_NAMES: [char; 7] = ['a', 'b', 'c', 'd', 'e', 'f', 'g'];
+
+// https://ja.wikipedia.org/wiki/7%E3%82%BB%E3%82%B0%E3%83%A1%E3%83%B3%E3%83%88%E3%83%87%E3%82%A3%E3%82%B9%E3%83%97%E3%83%AC%E3%82%A4#%E6%95%B0%E3%81%8B%E3%82%897%E3%82%BB%E3%82%B0%E3%83%A1%E3%83%B3%E3%83%88%E3%82%B3%E3%83%BC%E3%83%89%E3%81%B8%E3%81%AE%E5%A4%89%E6%8F%9B
+const SEVEN_SEGMENT_TABLE: [(char, u8); 38] = [
+    ('0', 0x7e),
+    ('1', 0x30),
+    ('2', 0x6d),
+    ('3', 0x79),
+    ('4', 0x33),
+    ('5', 0x5b),
+    ('6', 0x5f),
+    ('7', 0x70),
+    ('8', 0x7f),
+    ('9', 0x7b),
+    ('A', 0x77),
+    ('b', 0x1f),
+    ('C', 0x4e),
+    ('c', 0x0d),
+    ('d', 0x3d),
+    ('E', 0x4f),
+    ('F', 0x47),
+    ('G', 0x5e),
+    ('H', 0x37),
+    ('h', 0x17),
+    ('I', 0x06),
+    ('i', 0x04),
+    ('J', 0x3c),
+    ('L', 0x0e),
+    ('n', 0x15),
+    ('O', 0x7e),
+    ('o', 0x1d),
+    ('P', 0x67),
+    ('q', 0x73),
+    ('r', 0x05),
+    ('S', 0x5b),
+    ('t', 0x0f),
+    ('U', 0x3e),
+    ('u', 0x1c),
+    ('y', 0x3b),
+    ('-', 0x01),
+    ('_', 0x08),
+    (' ', 0x00),
+];
+
+// the lit segments of the character. the other case is used when the case is not in the table ( 'B' -> 'b' )
+// 0 = unknown
+pub fn seven_segment(c: char) -> u8
+{
+    let find = |c: char| SEVEN_SEGMENT_TABLE.iter().find(|x| x.0 == c).map(|x| x.1);
+
+    find(c)
+        .or_else(|| find(c.to_ascii_lowercase()))
+        .or_else(|| find(c.to_ascii_uppercase()))
+        .unwrap_or(0x00)
+}
+
+// segment = 'a' .. 'g'
+pub fn seven_segment_is_on(c: char, segment: char) -> bool
+{
+    match SEVEN_SEGMENT_NAMES.iter().position(|x| *x == segment.to_ascii_lowercase())
+    {
+        Some(i) => seven_segment(c) & (0x40 >> i) != 0x00,
+        None => false,
+    }
+}
+
+/*
+    the digit positions of the time and the date
+
+    hh, hl      -> Hour High / Low digit
+    mh, ml      -> Minute High / Low digit
+    sh, sl      -> Second High / Low digit
+    yr1 .. yr4  -> Year digits ( 2025 -> 2, 0, 2, 5 )
+    moh, mol    -> Month High / Low digit
+    dah, dal    -> Day High / Low digit
+    dy1 .. dy3  -> Day of year digits ( 045 -> 0, 4, 5 )
+*/
+
+pub const DIGIT_POSITIONS: [&str; 17] = [
+    "hh", "hl", "mh", "ml", "sh", "sl", "yr1", "yr2", "yr3", "yr4", "moh", "mol", "dah", "dal", "dy1", "dy2", "dy3",
+];
+
+// the digit of the position. hour12 = 1 .. 12 for hh, hl. None = unknown position
+pub fn digit(time: &NaiveDateTime, position: &str, hour12: bool) -> Option<u32>
+{
+    let hour = if hour12 { time.hour12().1 } else { time.hour() };
+
+    let year = time.year().rem_euclid(10000) as u32;
+    let day_of_year = time.ordinal();
+
+    let num = match position.to_ascii_lowercase().as_str()
+    {
+        "hh" => hour / 10,
+        "hl" => hour % 10,
+        "mh" => time.minute() / 10,
+        "ml" => time.minute() % 10,
+        "sh" => time.second() / 10,
+        "sl" => time.second() % 10,
+        "yr1" => year / 1000,
+        "yr2" => year / 100 % 10,
+        "yr3" => year / 10 % 10,
+        "yr4" => year % 10,
+        "moh" => time.month() / 10,
+        "mol" => time.month() % 10,
+        "dah" => time.day() / 10,
+        "dal" => time.day() % 10,
+        "dy1" => day_of_year / 100,
+        "dy2" => day_of_year / 10 % 10,
+        "dy3" => day_of_year % 10,
+        _ => return None,
+    };
+
+    Some(num)
+}
+
+/*
+    fourteen / sixteen segments
+
+     =a1= =a2=
+    |f|\h|i|j/|b|
+     =g1= =g2=
+    |e|/k|l|m\|c|
+     =d1= =d2=
+
+    the fourteen segments display has a ( = a1 + a2 ) and d ( = d1 + d2 ).
+*/
+
+pub const SIXTEEN_SEGMENT_NAMES: [&str; 16] =
+    ["a1", "a2", "b", "c", "d1", "d2", "e", "f", "g1", "g2", "h", "i", "j", "k", "l", "m"];
+
+const SIXTEEN_SEGMENT_TABLE: [(char, &str); 38] = [
+    ('A', "a1 a2 b c e f g1 g2"),
+    ('B', "a1 a2 b c d1 d2 g2 i l"),
+    ('C', "a1 a2 d1 d2 e f"),
+    ('D', "a1 a2 b c d1 d2 i l"),
+    ('E', "a1 a2 d1 d2 e f g1"),
+    ('F', "a1 a2 e f g1"),
+    ('G', "a1 a2 c d1 d2 e f g2"),
+    ('H', "b c e f g1 g2"),
+    ('I', "a1 a2 d1 d2 i l"),
+    ('J', "b c d1 d2 e"),
+    ('K', "e f g1 j m"),
+    ('L', "d1 d2 e f"),
+    ('M', "b c e f h j"),
+    ('N', "b c e f h m"),
+    ('O', "a1 a2 b c d1 d2 e f"),
+    ('P', "a1 a2 b e f g1 g2"),
+    ('Q', "a1 a2 b c d1 d2 e f m"),
+    ('R', "a1 a2 b e f g1 g2 m"),
+    ('S', "a1 a2 c d1 d2 f g1 g2"),
+    ('T', "a1 a2 i l"),
+    ('U', "b c d1 d2 e f"),
+    ('V', "e f j k"),
+    ('W', "b c e f k m"),
+    ('X', "h j k m"),
+    ('Y', "h j l"),
+    ('Z', "a1 a2 d1 d2 j k"),
+    ('0', "a1 a2 b c d1 d2 e f j k"),
+    ('1', "b c j"),
+    ('2', "a1 a2 b d1 d2 e g1 g2"),
+    ('3', "a1 a2 b c d1 d2 g2"),
+    ('4', "b c f g1 g2"),
+    ('5', "a1 a2 c d1 d2 f g1 g2"),
+    ('6', "a1 a2 c d1 d2 e f g1 g2"),
+    ('7', "a1 a2 b c"),
+    ('8', "a1 a2 b c d1 d2 e f g1 g2"),
+    ('9', "a1 a2 b c d1 d2 f g1 g2"),
+    ('-', "g1 g2"),
+    (' ', ""),
+];
+
+// the lit segments of the character. bit 15 .. 0 = a1 .. m. lower case letters as upper case
+// 0 = unknown
+pub fn sixteen_segment(c: char) -> u16
+{
+    match SIXTEEN_SEGMENT_TABLE.iter().find(|x| x.0 == c.to_ascii_uppercase())
+    {
+        Some((_, segments)) => segments
+            .split_whitespace()
+            .filter_map(|x| SIXTEEN_SEGMENT_NAMES.iter().position(|y| *y == x))
+            .fold(0x0000, |ret, i| ret | (0x8000 >> i)),
+        None => 0x0000,
+    }
+}
+
+// segment = "a1" .. "m" of sixteen segments, or "a", "d" of fourteen segments
+pub fn sixteen_segment_is_on(c: char, segment: &str) -> bool
+{
+    let segment = segment.to_ascii_lowercase();
+
+    let names: &[&str] = match segment.as_str()
+    {
+        "a" => &["a1", "a2"],
+        "d" => &["d1", "d2"],
+        x => &[x],
+    };
+
+    let bits = sixteen_segment(c);
+
+    names
+        .iter()
+        .filter_map(|x| SIXTEEN_SEGMENT_NAMES.iter().position(|y| y == x))
+        .any(|i| bits & (0x8000 >> i) != 0x0000)
+}
+
+/*
+    5 x 7 dot matrix. 7 rows from the top, 5 columns from the left
+*/
+
+pub const DOT_MATRIX_ROWS: usize = 7;
+pub const DOT_MATRIX_COLS: usize = 5;
+
+const DOT_MATRIX_TABLE: [(char, [&str; DOT_MATRIX_ROWS]); 38] = [
+    ('A', ["01110", "10001", "10001", "10001", "11111", "10001", "10001"]),
+    ('B', ["11110", "10001", "10001", "11110", "10001", "10001", "11110"]),
+    ('C', ["01110", "10001", "10000", "10000", "10000", "10001", "01110"]),
+    ('D', ["11100", "10010", "10001", "10001", "10001", "10010", "11100"]),
+    ('E', ["11111", "10000", "10000", "11110", "10000", "10000", "11111"]),
+    ('F', ["11111", "10000", "10000", "11110", "10000", "10000", "10000"]),
+    ('G', ["01110", "10001", "10000", "10111", "10001", "10001", "01111"]),
+    ('H', ["10001", "10001", "10001", "11111", "10001", "10001", "10001"]),
+    ('I', ["01110", "00100", "00100", "00100", "00100", "00100", "01110"]),
+    ('J', ["00111", "00010", "00010", "00010", "00010", "10010", "01100"]),
+    ('K', ["10001", "10010", "10100", "11000", "10100", "10010", "10001"]),
+    ('L', ["10000", "10000", "10000", "10000", "10000", "10000", "11111"]),
+    ('M', ["10001", "11011", "10101", "10101", "10001", "10001", "10001"]),
+    ('N', ["10001", "10001", "11001", "10101", "10011", "10001", "10001"]),
+    ('O', ["01110", "10001", "10001", "10001", "10001", "10001", "01110"]),
+    ('P', ["11110", "10001", "10001", "11110", "10000", "10000", "10000"]),
+    ('Q', ["01110", "10001", "10001", "10001", "10101", "10010", "01101"]),
+    ('R', ["11110", "10001", "10001", "11110", "10100", "10010", "10001"]),
+    ('S', ["01111", "10000", "10000", "01110", "00001", "00001", "11110"]),
+    ('T', ["11111", "00100", "00100", "00100", "00100", "00100", "00100"]),
+    ('U', ["10001", "10001", "10001", "10001", "10001", "10001", "01110"]),
+    ('V', ["10001", "10001", "10001", "10001", "10001", "01010", "00100"]),
+    ('W', ["10001", "10001", "10001", "10101", "10101", "10101", "01010"]),
+    ('X', ["10001", "10001", "01010", "00100", "01010", "10001", "10001"]),
+    ('Y', ["10001", "10001", "10001", "01010", "00100", "00100", "00100"]),
+    ('Z', ["11111", "00001", "00010", "00100", "01000", "10000", "11111"]),
+    ('0', ["01110", "10001", "10011", "10101", "11001", "10001", "01110"]),
+    ('1', ["00100", "01100", "00100", "00100", "00100", "00100", "01110"]),
+    ('2', ["01110", "10001", "00001", "00010", "00100", "01000", "11111"]),
+    ('3', ["11111", "00010", "00100", "00010", "00001", "10001", "01110"]),
+    ('4', ["00010", "00110", "01010", "10010", "11111", "00010", "00010"]),
+    ('5', ["11111", "10000", "11110", "00001", "00001", "10001", "01110"]),
+    ('6', ["00110", "01000", "10000", "11110", "10001", "10001", "01110"]),
+    ('7', ["11111", "00001", "00010", "00100", "01000", "01000", "01000"]),
+    ('8', ["01110", "10001", "10001", "01110", "10001", "10001", "01110"]),
+    ('9', ["01110", "10001", "10001", "01111", "00001", "00010", "01100"]),
+    ('-', ["00000", "00000", "00000", "11111", "00000", "00000", "00000"]),
+    (' ', ["00000", "00000", "00000", "00000", "00000", "00000", "00000"]),
+];
+
+// lower case letters as upper case. unknown = all off
+pub fn dot_matrix_is_on(c: char, row: usize, col: usize) -> bool
+{
+    DOT_MATRIX_TABLE
+        .iter()
+        .find(|x| x.0 == c.to_ascii_uppercase())
+        .and_then(|x| x.1.get(row))
+        .and_then(|x| x.as_bytes().get(col))
+        .is_some_and(|x| *x == b'1')
+}
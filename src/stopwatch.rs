@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/*
+    A stopwatch ( chronograph ) measured by the monotonic clock.
+    Independent of the time of the clock ( time zone, FIX_TIME, the move to the new time, ... ).
+
+    start / stop    accumulates the running time
+    lap             records the elapsed time. only while running
+    reset           clears the elapsed time and the laps. only while stopped
+
+    `now` is given by the caller. Instant::now() in the application.
+*/
+
+#[derive(Debug, Clone, Default)]
+pub struct Stopwatch
+{
+    started:     Option<Instant>,
+    accumulated: Duration,
+    laps:        Vec<Duration>,
+}
+
+impl Stopwatch
+{
+    pub const fn new() -> Self
+    {
+        Self {
+            started:     None,
+            accumulated: Duration::ZERO,
+            laps:        Vec::new(),
+        }
+    }
+
+    pub fn is_running(&self) -> bool
+    {
+        self.started.is_some()
+    }
+
+    pub fn is_reset(&self) -> bool
+    {
+        !self.is_running() && self.accumulated.is_zero() && self.laps.is_empty()
+    }
+
+    pub fn start(&mut self, now: Instant)
+    {
+        if self.started.is_none()
+        {
+            self.started = Some(now);
+        }
+    }
+
+    pub fn stop(&mut self, now: Instant)
+    {
+        if let Some(x) = self.started.take()
+        {
+            self.accumulated += now.saturating_duration_since(x);
+        }
+    }
+
+    pub fn toggle(&mut self, now: Instant)
+    {
+        if self.is_running()
+        {
+            self.stop(now);
+        }
+        else
+        {
+            self.start(now);
+        }
+    }
+
+    pub fn lap(&mut self, now: Instant)
+    {
+        if self.is_running()
+        {
+            let elapsed = self.elapsed(now);
+            self.laps.push(elapsed);
+        }
+    }
+
+    pub fn reset(&mut self)
+    {
+        if !self.is_running()
+        {
+            self.accumulated = Duration::ZERO;
+            self.laps.clear();
+        }
+    }
+
+    pub fn elapsed(&self, now: Instant) -> Duration
+    {
+        match self.started
+        {
+            Some(x) => self.accumulated + now.saturating_duration_since(x),
+            None => self.accumulated,
+        }
+    }
+
+    // the elapsed times at the laps
+    pub fn laps(&self) -> &[Duration]
+    {
+        &self.laps
+    }
+
+    // the time of the last lap ( split ). from the previous lap or the start
+    pub fn last_lap(&self) -> Option<Duration>
+    {
+        match self.laps.as_slice()
+        {
+            [] => None,
+            [x] => Some(*x),
+            [.., x, y] => Some(y.saturating_sub(*x)),
+        }
+    }
+}
+
+// "00:01:23.4"
+pub fn format_elapsed(elapsed: Duration) -> String
+{
+    let secs = elapsed.as_secs();
+
+    format!(
+        "{:02}:{:02}:{:02}.{}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        elapsed.subsec_millis() / 100
+    )
+}
+
+/*
+    The source of the rotation of a hand of the stopwatch.
+
+    driver              range   one turn
+    ------------------  ------  ---------------------------------------
+    chrono_second       60      1 minute                ( chronograph seconds hand )
+    chrono_minute30     30      30 minutes              ( 30-minute counter )
+    chrono_minute       60      1 hour
+    chrono_hour12       12      12 hours                ( 12-hour counter )
+*/
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChronoDriver
+{
+    ChronoSecond,
+    ChronoMinute30,
+    ChronoMinute,
+    ChronoHour12,
+}
+
+impl ChronoDriver
+{
+    // the number of the units of one turn
+    pub fn range(&self) -> f64
+    {
+        match self
+        {
+            ChronoDriver::ChronoSecond => 60.0,
+            ChronoDriver::ChronoMinute30 => 30.0,
+            ChronoDriver::ChronoMinute => 60.0,
+            ChronoDriver::ChronoHour12 => 12.0,
+        }
+    }
+
+    // the length of the unit in seconds
+    pub fn unit_secs(&self) -> f64
+    {
+        match self
+        {
+            ChronoDriver::ChronoSecond => 1.0,
+            ChronoDriver::ChronoMinute30 | ChronoDriver::ChronoMinute => 60.0,
+            ChronoDriver::ChronoHour12 => 60.0 * 60.0,
+        }
+    }
+
+    // the current value in the units of `range()`. 0.0 <= value < range
+    // the fraction of the unit is included if `smooth`
+    pub fn value(&self, elapsed: Duration, smooth: bool) -> f64
+    {
+        let value = (elapsed.as_secs_f64() / self.unit_secs()) % self.range();
+
+        if smooth
+        {
+            value
+        }
+        else
+        {
+            value.floor()
+        }
+    }
+}
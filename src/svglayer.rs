@@ -0,0 +1,345 @@
+use quick_xml::events::BytesStart;
+use regex::Regex;
+use std::error::Error;
+use std::io::Cursor;
+use std::sync::LazyLock;
+
+type XmlInputReader<'a> = quick_xml::Reader<&'a [u8]>;
+
+/*
+    A layer is an element at any depth ( sub-layer ) identified by one of
+
+    <g inkscape:groupmode="layer" inkscape:label="base">    Inkscape
+    <g data-svgclock-layer="base">                          hand written
+    <g svgclock:layer="base">                               xmlns:svgclock="https://github.com/zuntan/svgclock-rs/"
+                                                            ( any prefix bound to the namespace )
+    <g id="base">                                           Figma, Illustrator ( id="long_x5F_handle" ), ...
+
+    `known` is the names of the layers of the clock ( base, long_handle, ... and the layers declared
+    in the config ). an id is a layer name only if it is known.
+
+    `ns_keys` is the keys of the layer attribute of the namespace in the document. see get_layer_ns_keys
+*/
+
+const LAYER_ATTR_KEY_GROUPMODE: &str = "inkscape:groupmode";
+const LAYER_ATTR_VAL_GROUPMODE: &str = "layer";
+const LAYER_ATTR_KEY_LABEL: &str = "inkscape:label";
+const LAYER_ATTR_KEY_DATA: &str = "data-svgclock-layer";
+const LAYER_ATTR_NS: &str = "https://github.com/zuntan/svgclock-rs/";
+const LAYER_ATTR_NS_LOCAL_NAME: &str = "layer";
+const LAYER_ATTR_KEY_ID: &str = "id";
+
+pub fn get_attr_string(tag: &BytesStart<'_>, key: &str) -> Option<String>
+{
+    if let Ok(Some(attr)) = tag.try_get_attribute(key.as_bytes())
+        && let Ok(val) = attr.unescape_value()
+    {
+        Some(val.to_string())
+    }
+    else
+    {
+        None
+    }
+}
+
+fn decode_layer_id(id: &str) -> String
+{
+    // Illustrator escapes the characters of the layer name. "long_x5F_handle" -> "long_handle"
+
+    static RE_ESCAPE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"_x([0-9A-Fa-f]{2})_").unwrap());
+
+    RE_ESCAPE
+        .replace_all(id, |caps: &regex::Captures| {
+            match u8::from_str_radix(&caps[1], 16)
+            {
+                Ok(x) => String::from(x as char),
+                _ => String::from(&caps[0]),
+            }
+        })
+        .to_string()
+}
+
+// the keys of the layer attribute. "svgclock:layer", ... for the prefixes bound to LAYER_ATTR_NS
+pub fn get_layer_ns_keys(src_buf: &[u8]) -> Vec<String>
+{
+    let mut ret = Vec::<String>::new();
+
+    let mut r_src = XmlInputReader::from_reader(src_buf);
+
+    loop
+    {
+        match r_src.read_event()
+        {
+            Ok(quick_xml::events::Event::Eof) | Err(_) => break,
+            Ok(quick_xml::events::Event::Start(ref tag) | quick_xml::events::Event::Empty(ref tag)) =>
+            {
+                for attr in tag.attributes().flatten()
+                {
+                    if let Some(prefix) = attr.key.as_ref().strip_prefix(b"xmlns:")
+                        && attr.unescape_value().is_ok_and(|x| x.trim_end_matches('/') == LAYER_ATTR_NS.trim_end_matches('/'))
+                    {
+                        let key = format!("{}:{}", String::from_utf8_lossy(prefix), LAYER_ATTR_NS_LOCAL_NAME);
+
+                        if !ret.contains(&key)
+                        {
+                            ret.push(key);
+                        }
+                    }
+                }
+            },
+            _ =>
+            {},
+        }
+    }
+
+    ret
+}
+
+pub fn get_layer_names(tag: &BytesStart<'_>, ns_keys: &[String]) -> Vec<String>
+{
+    let mut ret = Vec::<String>::new();
+
+    if tag.name().as_ref() == "g".as_bytes()
+        && get_attr_string(tag, LAYER_ATTR_KEY_GROUPMODE).as_deref() == Some(LAYER_ATTR_VAL_GROUPMODE)
+        && let Some(x) = get_attr_string(tag, LAYER_ATTR_KEY_LABEL)
+    {
+        ret.push(x);
+    }
+
+    for key in std::iter::once(LAYER_ATTR_KEY_DATA).chain(ns_keys.iter().map(|x| x.as_str()))
+    {
+        if let Some(x) = get_attr_string(tag, key)
+        {
+            ret.push(x);
+        }
+    }
+
+    if let Some(x) = get_attr_string(tag, LAYER_ATTR_KEY_ID)
+    {
+        ret.push(decode_layer_id(&x));
+    }
+
+    ret
+}
+
+pub fn is_layer_name(tag: &BytesStart<'_>, name: &str, ns_keys: &[String]) -> bool
+{
+    get_layer_names(tag, ns_keys).iter().any(|x| x == name)
+}
+
+fn is_known_layer(tag: &BytesStart<'_>, known: &[String], ns_keys: &[String]) -> bool
+{
+    // an id is a layer name only if it is the name of a known layer ( not "defs1", "layer1", ... )
+
+    get_layer_names(tag, ns_keys).iter().any(|x| known.contains(x))
+}
+
+fn is_shared_element(tag: &BytesStart<'_>) -> bool
+{
+    // elements referenced from the other layers ( gradients, clip paths, css, ... )
+
+    matches!(tag.name().as_ref(), b"defs" | b"style")
+}
+
+fn find_layer_ancestors(src_buf: &[u8], name: &str, ns_keys: &[String]) -> Result<Vec<usize>, Box<dyn Error>>
+{
+    // the indexes ( order of the start tags ) of the elements containing the target layer
+
+    let mut ret = Vec::<usize>::new();
+
+    let mut stack = Vec::<usize>::new();
+    let mut index = 0;
+
+    let mut r_src = XmlInputReader::from_reader(src_buf);
+
+    loop
+    {
+        match r_src.read_event()?
+        {
+            quick_xml::events::Event::Eof => break,
+            quick_xml::events::Event::Start(ref tag) =>
+            {
+                if is_layer_name(tag, name, ns_keys)
+                {
+                    ret.extend(stack.iter());
+                }
+
+                stack.push(index);
+                index += 1;
+            },
+            quick_xml::events::Event::Empty(ref tag) if is_layer_name(tag, name, ns_keys) =>
+            {
+                ret.extend(stack.iter());
+            },
+            quick_xml::events::Event::End(_) =>
+            {
+                stack.pop();
+            },
+            _ =>
+            {},
+        }
+    }
+
+    ret.sort();
+    ret.dedup();
+
+    Ok(ret)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum FilterState
+{
+    Root,     // <svg>. keeps only the target and the shared elements
+    Ancestor, // contains the target. keeps only the target and the shared elements
+    Output,   // keeps everything but the other known layers ( extracted by themselves )
+    Skip,
+}
+
+pub fn filter_xml_layer(src_buf: &[u8], name: &str, known: &[String]) -> Result<Option<Vec<u8>>, Box<dyn Error>>
+{
+    /*
+        Extracts the target layer. The layer may be nested in other layers (sub-layer).
+
+        <svg>                               Root
+          <defs/>                           kept
+          <rect/>                           removed
+          <g label="base"/>                 removed
+          <g label="hands" transform="..">  Ancestor. kept without its own drawing
+            <defs/>                         kept
+            <path/>                         removed
+            <g label="second_handle"/>      Output
+          </g>
+        </svg>
+
+        A known layer inside the output ( <g label="second_handle"/> in <g label="base"/> ) is removed,
+        not to be drawn twice.
+    */
+
+    let ns_keys = get_layer_ns_keys(src_buf);
+
+    let ancestors = find_layer_ancestors(src_buf, name, &ns_keys)?;
+
+    let mut writer = quick_xml::Writer::new(Cursor::new(Vec::<u8>::new()));
+
+    let mut found = false;
+
+    let mut states = Vec::<FilterState>::new();
+    let mut index = 0;
+
+    let mut r_src = XmlInputReader::from_reader(src_buf);
+
+    let classify = |tag: &BytesStart<'_>, parent: Option<FilterState>, index: Option<usize>| {
+        match parent
+        {
+            None => FilterState::Root,
+            Some(FilterState::Root | FilterState::Ancestor) if is_layer_name(tag, name, &ns_keys) => FilterState::Output,
+            Some(FilterState::Root | FilterState::Ancestor)
+                if index.is_some_and(|x| ancestors.binary_search(&x).is_ok()) =>
+            {
+                FilterState::Ancestor
+            },
+            Some(FilterState::Root | FilterState::Ancestor) =>
+            {
+                if is_shared_element(tag)
+                {
+                    FilterState::Output
+                }
+                else
+                {
+                    FilterState::Skip
+                }
+            },
+            Some(FilterState::Output) =>
+            {
+                if is_known_layer(tag, known, &ns_keys) && !is_layer_name(tag, name, &ns_keys)
+                {
+                    FilterState::Skip
+                }
+                else
+                {
+                    FilterState::Output
+                }
+            },
+            Some(FilterState::Skip) => FilterState::Skip,
+        }
+    };
+
+    let is_target = |state: FilterState, parent: Option<FilterState>, tag: &BytesStart<'_>| {
+        state == FilterState::Output && parent != Some(FilterState::Output) && is_layer_name(tag, name, &ns_keys)
+    };
+
+    loop
+    {
+        let event = r_src.read_event();
+
+        match event
+        {
+            Ok(quick_xml::events::Event::Eof) => break,
+
+            Ok(evt) =>
+            {
+                let parent = states.last().copied();
+
+                match evt
+                {
+                    quick_xml::events::Event::Start(ref tag) =>
+                    {
+                        let state = classify(tag, parent, Some(index));
+
+                        index += 1;
+
+                        found |= is_target(state, parent, tag);
+
+                        if state != FilterState::Skip
+                        {
+                            assert!(writer.write_event(evt.borrow()).is_ok())
+                        }
+
+                        states.push(state);
+                    },
+                    quick_xml::events::Event::End(ref _tag) =>
+                    {
+                        if states.pop().is_some_and(|x| x != FilterState::Skip)
+                        {
+                            assert!(writer.write_event(evt.borrow()).is_ok())
+                        }
+                    },
+                    quick_xml::events::Event::Empty(ref tag) =>
+                    {
+                        // a layer without children. <path id="long_handle" ... />
+
+                        let state = classify(tag, parent, None);
+
+                        found |= is_target(state, parent, tag);
+
+                        if state != FilterState::Skip
+                        {
+                            assert!(writer.write_event(evt.borrow()).is_ok())
+                        }
+                    },
+                    _ =>
+                    {
+                        if parent != Some(FilterState::Skip)
+                        {
+                            assert!(writer.write_event(evt.borrow()).is_ok())
+                        }
+                    },
+                }
+            },
+
+            Err(e) => return Err(Box::new(e)),
+        }
+    }
+
+    if found
+    {
+        let inner_buf = writer.into_inner().into_inner();
+        Ok(Some(inner_buf))
+    }
+    else
+    {
+        Ok(None)
+    }
+}
+
@@ -0,0 +1,413 @@
+use glam::{DAffine2, DMat2, DVec2};
+use quick_xml::events::{BytesStart, Event};
+use regex::Regex;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+pub fn parse_float_list(val: &str) -> Vec<f64>
+{
+    static RE_FLOAT: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"[-+]?([0-9]*\.[0-9]+|[0-9]+\.?[0-9]*)([eE][-+]?[0-9]+)?").unwrap()
+    });
+
+    let arg: Vec<f64> = RE_FLOAT
+        .captures_iter(val)
+        .filter_map(|x| f64::from_str(&x[0]).ok())
+        .collect();
+
+    arg
+}
+
+/*
+    transform-list (SVG 1.1)
+        see https://www.w3.org/TR/SVG11/coords.html#TransformAttribute
+
+    transform-list:
+        wsp* transforms? wsp*
+    transforms:
+        transform | transform comma-wsp+ transforms
+    transform:
+        matrix | translate | scale | rotate | skewX | skewY
+
+    matrix(a b c d e f)
+    translate(tx [ty])      ty = 0
+    scale(sx [sy])          sy = sx
+    rotate(a [cx cy])       degrees
+    skewX(a)                degrees
+    skewY(a)                degrees
+*/
+
+struct Scanner<'a>
+{
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a>
+{
+    fn new(src: &'a str) -> Self
+    {
+        Self {
+            src: src.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8>
+    {
+        self.src.get(self.pos).copied()
+    }
+
+    fn is_eof(&self) -> bool
+    {
+        self.pos >= self.src.len()
+    }
+
+    fn skip_wsp(&mut self) -> bool
+    {
+        let start = self.pos;
+
+        while let Some(c) = self.peek()
+            && (c == b' ' || c == b'\t' || c == b'\r' || c == b'\n')
+        {
+            self.pos += 1;
+        }
+
+        self.pos != start
+    }
+
+    fn skip_comma_wsp(&mut self) -> bool
+    {
+        let mut ret = self.skip_wsp();
+
+        if self.peek() == Some(b',')
+        {
+            self.pos += 1;
+            self.skip_wsp();
+            ret = true;
+        }
+
+        ret
+    }
+
+    fn expect(&mut self, c: u8) -> bool
+    {
+        if self.peek() == Some(c)
+        {
+            self.pos += 1;
+            true
+        }
+        else
+        {
+            false
+        }
+    }
+
+    fn name(&mut self) -> Option<&'a str>
+    {
+        let start = self.pos;
+
+        while let Some(c) = self.peek()
+            && c.is_ascii_alphabetic()
+        {
+            self.pos += 1;
+        }
+
+        if self.pos != start
+        {
+            std::str::from_utf8(&self.src[start .. self.pos]).ok()
+        }
+        else
+        {
+            None
+        }
+    }
+
+    fn digits(&mut self) -> usize
+    {
+        let start = self.pos;
+
+        while let Some(c) = self.peek()
+            && c.is_ascii_digit()
+        {
+            self.pos += 1;
+        }
+
+        self.pos - start
+    }
+
+    fn number(&mut self) -> Option<f64>
+    {
+        let start = self.pos;
+
+        if let Some(b'+' | b'-') = self.peek()
+        {
+            self.pos += 1;
+        }
+
+        let mut len = self.digits();
+
+        if self.peek() == Some(b'.')
+        {
+            self.pos += 1;
+            len += self.digits();
+        }
+
+        if len == 0
+        {
+            self.pos = start;
+            return None;
+        }
+
+        if let Some(b'e' | b'E') = self.peek()
+        {
+            let mark = self.pos;
+
+            self.pos += 1;
+
+            if let Some(b'+' | b'-') = self.peek()
+            {
+                self.pos += 1;
+            }
+
+            if self.digits() == 0
+            {
+                self.pos = mark;
+            }
+        }
+
+        std::str::from_utf8(&self.src[start .. self.pos])
+            .ok()
+            .and_then(|x| f64::from_str(x).ok())
+    }
+}
+
+fn make_transform(op: &str, arg: &[f64]) -> Result<DAffine2, String>
+{
+    let ret = match (op.to_lowercase().as_str(), arg.len())
+    {
+        ("matrix", 6) => DAffine2::from_mat2_translation(
+            DMat2::from_cols(DVec2::new(arg[0], arg[1]), DVec2::new(arg[2], arg[3])),
+            DVec2::new(arg[4], arg[5]),
+        ),
+        ("translate", 1) => DAffine2::from_translation(DVec2::new(arg[0], 0.0)),
+        ("translate", 2) => DAffine2::from_translation(DVec2::new(arg[0], arg[1])),
+        ("scale", 1) => DAffine2::from_scale(DVec2::new(arg[0], arg[0])),
+        ("scale", 2) => DAffine2::from_scale(DVec2::new(arg[0], arg[1])),
+        ("rotate", 1) => DAffine2::from_angle(arg[0].to_radians()),
+        ("rotate", 3) =>
+        {
+            let c = DVec2::new(arg[1], arg[2]);
+
+            DAffine2::from_translation(c)
+                * DAffine2::from_angle(arg[0].to_radians())
+                * DAffine2::from_translation(-c)
+        },
+        ("skewx", 1) => DAffine2::from_mat2(DMat2::from_cols(
+            DVec2::new(1.0, 0.0),
+            DVec2::new(arg[0].to_radians().tan(), 1.0),
+        )),
+        ("skewy", 1) => DAffine2::from_mat2(DMat2::from_cols(
+            DVec2::new(1.0, arg[0].to_radians().tan()),
+            DVec2::new(0.0, 1.0),
+        )),
+        ("matrix" | "translate" | "scale" | "rotate" | "skewx" | "skewy", _) =>
+        {
+            return Err(format!("{}: wrong number of arguments ({})", op, arg.len()));
+        },
+        _ =>
+        {
+            return Err(format!("unknown transform `{}`", op));
+        },
+    };
+
+    Ok(ret)
+}
+
+pub fn parse_svg_transform(transform: &str) -> Result<DAffine2, String>
+{
+    let mut sc = Scanner::new(transform);
+
+    let mut ret = DAffine2::IDENTITY;
+
+    sc.skip_wsp();
+
+    while !sc.is_eof()
+    {
+        let pos = sc.pos;
+
+        let op = match sc.name()
+        {
+            Some(x) => x,
+            None => return Err(format!("syntax error at {}", pos)),
+        };
+
+        sc.skip_wsp();
+
+        if !sc.expect(b'(')
+        {
+            return Err(format!("{}: `(` expected at {}", op, sc.pos));
+        }
+
+        sc.skip_wsp();
+
+        let mut arg = Vec::<f64>::new();
+
+        while let Some(x) = sc.number()
+        {
+            arg.push(x);
+            sc.skip_comma_wsp();
+        }
+
+        if !sc.expect(b')')
+        {
+            return Err(format!("{}: `)` expected at {}", op, sc.pos));
+        }
+
+        ret *= make_transform(op, &arg)?;
+
+        sc.skip_comma_wsp();
+    }
+
+    Ok(ret)
+}
+
+pub fn parse_svg_transform_value(transform: &str) -> Option<DAffine2>
+{
+    if transform.trim() == ""
+    {
+        None
+    }
+    else
+    {
+        // an invalid transform-list is ignored ( = no transform )
+        parse_svg_transform(transform).ok()
+    }
+}
+
+pub fn get_transform_affine(tag: &BytesStart<'_>) -> DAffine2
+{
+    if let Ok(Some(attr)) = tag.try_get_attribute("transform".as_bytes())
+        && let Ok(attr_transform) = std::str::from_utf8(attr.value.as_ref())
+        && let Some(x) = parse_svg_transform_value(attr_transform)
+    {
+        x
+    }
+    else
+    {
+        DAffine2::IDENTITY
+    }
+}
+
+fn get_circle_center(tag: &BytesStart<'_>) -> Option<DVec2>
+{
+    let get_attr = |key: &str| {
+        if let Ok(Some(attr)) = tag.try_get_attribute(key.as_bytes())
+            && let Ok(val) = std::str::from_utf8(attr.value.as_ref())
+            && let Some(x) = parse_float_list(val).first()
+        {
+            Some(*x)
+        }
+        else
+        {
+            None
+        }
+    };
+
+    match tag.name().as_ref()
+    {
+        b"circle" | b"ellipse" => Some(DVec2::new(
+            get_attr("cx").unwrap_or_default(),
+            get_attr("cy").unwrap_or_default(),
+        )),
+        b"path" =>
+        {
+            // circle drawn by Inkscape 0.92 and before
+            // <path sodipodi:type="arc" sodipodi:cx=".." sodipodi:cy=".." ...>
+
+            if let Some(cx) = get_attr("sodipodi:cx")
+                && let Some(cy) = get_attr("sodipodi:cy")
+            {
+                Some(DVec2::new(cx, cy))
+            }
+            else
+            {
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+/*
+    Returns the center of the circle ( <circle>, <ellipse> or Inkscape's arc <path> )
+    inside the element selected by `is_target( tag, depth )`, in the coordinate of the document.
+    The transforms of all the ancestors and of the circle itself are applied.
+    If there are several circles, the last one is used.
+
+    depth: 1 = <svg>
+*/
+pub fn find_center<F>(src_buf: &[u8], is_target: F) -> Result<Option<DVec2>, quick_xml::Error>
+where
+    F: Fn(&BytesStart<'_>, usize) -> bool,
+{
+    let mut ret: Option<DVec2> = None;
+
+    let mut affines: Vec<DAffine2> = Vec::new();
+    let mut target_depth: Option<usize> = None;
+
+    let mut r_src = quick_xml::Reader::from_reader(src_buf);
+
+    loop
+    {
+        match r_src.read_event()?
+        {
+            Event::Eof => break,
+            Event::Start(ref tag) =>
+            {
+                let parent = affines.last().copied().unwrap_or(DAffine2::IDENTITY);
+                let affine = parent * get_transform_affine(tag);
+
+                affines.push(affine);
+
+                if target_depth.is_some()
+                {
+                    if let Some(x) = get_circle_center(tag)
+                    {
+                        ret = Some(affine.transform_point2(x));
+                    }
+                }
+                else if is_target(tag, affines.len())
+                {
+                    target_depth = Some(affines.len());
+                }
+            },
+            Event::Empty(ref tag) =>
+            {
+                // a circle in the target, or the target is a circle itself
+
+                if (target_depth.is_some() || is_target(tag, affines.len() + 1))
+                    && let Some(x) = get_circle_center(tag)
+                {
+                    let parent = affines.last().copied().unwrap_or(DAffine2::IDENTITY);
+                    let affine = parent * get_transform_affine(tag);
+
+                    ret = Some(affine.transform_point2(x));
+                }
+            },
+            Event::End(_) =>
+            {
+                if target_depth == Some(affines.len())
+                {
+                    target_depth = None;
+                }
+
+                affines.pop();
+            },
+            _ =>
+            {},
+        }
+    }
+
+    Ok(ret)
+}
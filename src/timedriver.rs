@@ -0,0 +1,182 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, TimeDelta, Timelike};
+use serde::{Deserialize, Serialize};
+
+/*
+    The source of the rotation of a hand.
+
+    driver              range   one turn
+    ------------------  ------  ---------------------------------------
+    hour12              12      12 hours                ( short hand )
+    hour24              24      24 hours                ( GMT hand )
+    minute_of_hour      60      1 hour                  ( long hand )
+    second_of_minute    60      1 minute                ( second hand )
+    day_of_week         7       1 week. Sunday = 0
+    day_of_month        31      1 month. the 1st = 0. a hand of 31 positions
+    month               12      1 year. January = 0
+    year_progress       365/366 1 year. January 1st = 0
+*/
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeDriver
+{
+    Hour12,
+    Hour24,
+    MinuteOfHour,
+    SecondOfMinute,
+    DayOfWeek,
+    DayOfMonth,
+    Month,
+    YearProgress,
+}
+
+fn days_in_month(dt: &NaiveDateTime) -> u32
+{
+    let (y, m) = if dt.month() == 12
+    {
+        (dt.year() + 1, 1)
+    }
+    else
+    {
+        (dt.year(), dt.month() + 1)
+    };
+
+    NaiveDate::from_ymd_opt(y, m, 1)
+        .and_then(|x| x.pred_opt())
+        .map(|x| x.day())
+        .unwrap_or(31)
+}
+
+fn days_in_year(dt: &NaiveDateTime) -> u32
+{
+    if NaiveDate::from_ymd_opt(dt.year(), 2, 29).is_some()
+    {
+        366
+    }
+    else
+    {
+        365
+    }
+}
+
+// nanosecond() >= 1e9 in a leap second
+fn secs_of_day(dt: &NaiveDateTime) -> f64
+{
+    dt.num_seconds_from_midnight() as f64 + dt.nanosecond().min(999_999_999) as f64 / 1e9
+}
+
+impl TimeDriver
+{
+    // the number of the units of one turn
+    pub fn range(&self, dt: &NaiveDateTime) -> f64
+    {
+        match self
+        {
+            TimeDriver::Hour12 => 12.0,
+            TimeDriver::Hour24 => 24.0,
+            TimeDriver::MinuteOfHour => 60.0,
+            TimeDriver::SecondOfMinute => 60.0,
+            TimeDriver::DayOfWeek => 7.0,
+            TimeDriver::DayOfMonth => 31.0,
+            TimeDriver::Month => 12.0,
+            TimeDriver::YearProgress => days_in_year(dt) as f64,
+        }
+    }
+
+    // the current value in the units of `range()`. 0.0 <= value < range
+    // the fraction of the unit is included if `smooth`
+    pub fn value(&self, dt: &NaiveDateTime, smooth: bool) -> f64
+    {
+        let secs_of_day = secs_of_day(dt);
+        let frac_of_day = secs_of_day / (24.0 * 60.0 * 60.0);
+
+        let (unit, frac) = match self
+        {
+            TimeDriver::Hour12 => (dt.hour12().1 as f64 % 12.0, (secs_of_day % 3600.0) / 3600.0),
+            TimeDriver::Hour24 => (dt.hour() as f64, (secs_of_day % 3600.0) / 3600.0),
+            TimeDriver::MinuteOfHour => (dt.minute() as f64, (secs_of_day % 60.0) / 60.0),
+            TimeDriver::SecondOfMinute => (dt.second().min(59) as f64, secs_of_day.fract()),
+            TimeDriver::DayOfWeek => (dt.weekday().num_days_from_sunday() as f64, frac_of_day),
+            TimeDriver::DayOfMonth => (dt.day0() as f64, frac_of_day),
+            TimeDriver::Month => (
+                dt.month0() as f64,
+                (dt.day0() as f64 + frac_of_day) / days_in_month(dt) as f64,
+            ),
+            TimeDriver::YearProgress => (dt.ordinal0() as f64, frac_of_day),
+        };
+
+        if smooth
+        {
+            unit + frac
+        }
+        else
+        {
+            unit
+        }
+    }
+
+    // the length of the unit in seconds
+    pub fn unit_secs(&self, dt: &NaiveDateTime) -> f64
+    {
+        match self
+        {
+            TimeDriver::Hour12 | TimeDriver::Hour24 => 60.0 * 60.0,
+            TimeDriver::MinuteOfHour => 60.0,
+            TimeDriver::SecondOfMinute => 1.0,
+            TimeDriver::DayOfWeek | TimeDriver::DayOfMonth | TimeDriver::YearProgress => 24.0 * 60.0 * 60.0,
+            TimeDriver::Month => days_in_month(dt) as f64 * 24.0 * 60.0 * 60.0,
+        }
+    }
+
+    // the seconds since the start of the current turn ( for the snap back )
+    // the months are not of the same length. counted from January 1st
+    pub fn elapsed_secs(&self, dt: &NaiveDateTime) -> f64
+    {
+        match self
+        {
+            TimeDriver::Month => dt.ordinal0() as f64 * 24.0 * 60.0 * 60.0 + secs_of_day(dt),
+            _ => self.value(dt, true) * self.unit_secs(dt),
+        }
+    }
+
+    // 0.0 <= fraction < 1.0 of one turn
+    pub fn fraction(&self, dt: &NaiveDateTime, smooth: bool) -> f64
+    {
+        self.value(dt, smooth) / self.range(dt)
+    }
+
+    // the position of a wheel turning in discrete steps ( date wheel, ... )
+    // the move from the previous step takes `duration` seconds from the start of the step
+    // ex. day_of_month 9.0 -> 9.5 -> 10.0 at 00:00:00 -> 00:00:00.5 -> 00:00:01 ( duration = 1.0 )
+    //     day_of_month 29.0 -> 30.0 -> 31.0 at the end of April ( 31.0 = 0.0 of the next turn )
+    pub fn step_position(&self, dt: &NaiveDateTime, duration: f64) -> f64
+    {
+        let value = self.value(dt, false);
+        let elapsed = (self.value(dt, true) - value) * self.unit_secs(dt);
+
+        if duration > 0.0 && elapsed < duration
+        {
+            let dt_prev = *dt - TimeDelta::milliseconds(((elapsed + 1.0) * 1000.0) as i64);
+            let value_prev = self.value(&dt_prev, false);
+
+            let mut delta = value - value_prev;
+
+            if delta < 0.0
+            {
+                delta += self.range(&dt_prev);
+            }
+
+            value_prev + delta * tween::QuadInOut.tween(1.0, (elapsed / duration) as f32)
+        }
+        else
+        {
+            value
+        }
+    }
+
+    // the hands of the time move smoothly, the hands of the date jump at midnight
+    pub fn is_smooth_default(&self) -> bool
+    {
+        !matches!(self, TimeDriver::DayOfWeek | TimeDriver::DayOfMonth | TimeDriver::Month)
+    }
+}
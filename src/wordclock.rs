@@ -0,0 +1,153 @@
+use chrono::{NaiveDateTime, Timelike};
+
+/*
+    Word clock. The words of the grid to light up.
+
+    English     "IT IS TWENTY FIVE MINUTES PAST TEN"
+
+        it, is                      always
+        m1 .. m20                   ONE .. TWENTY of the minutes ( 23 = m20 + m3 )
+        quarter, half
+        minute, minutes             after the number of the minutes
+        past, to
+        h1 .. h12                   ONE .. TWELVE of the hour
+        oclock                      at the hour
+
+    Japanese    "午後 十 時 二十 五 分"
+
+        am, pm                      午前, 午後
+        h1 .. h12                   一 .. 十二 of the hour
+        ji                          時
+        t1 .. t5                    十, 二十 .. 五十 of the minutes
+        u1 .. u9                    一 .. 九 of the minutes
+        fun                         分
+        half                        半
+        choudo                      ちょうど. at the hour
+
+    resolution = 5 ( the minutes are rounded down to five minutes ) or 1
+*/
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum WordClockLang
+{
+    En,
+    Ja,
+}
+
+impl WordClockLang
+{
+    // "en", "ja"
+    pub fn from_name(name: &str) -> Option<Self>
+    {
+        match name.to_ascii_lowercase().as_str()
+        {
+            "en" => Some(WordClockLang::En),
+            "ja" => Some(WordClockLang::Ja),
+            _ => None,
+        }
+    }
+}
+
+const EN_MINUTES: [&str; 20] = [
+    "m1", "m2", "m3", "m4", "m5", "m6", "m7", "m8", "m9", "m10", "m11", "m12", "m13", "m14", "m15", "m16", "m17", "m18",
+    "m19", "m20",
+];
+
+const HOURS: [&str; 12] = ["h1", "h2", "h3", "h4", "h5", "h6", "h7", "h8", "h9", "h10", "h11", "h12"];
+
+const JA_TENS: [&str; 5] = ["t1", "t2", "t3", "t4", "t5"];
+const JA_UNITS: [&str; 9] = ["u1", "u2", "u3", "u4", "u5", "u6", "u7", "u8", "u9"];
+
+// the words to light up
+pub fn word_clock_words(lang: WordClockLang, time: &NaiveDateTime, resolution: u32) -> Vec<&'static str>
+{
+    let minute = if resolution == 1 { time.minute() } else { time.minute() / 5 * 5 };
+
+    match lang
+    {
+        WordClockLang::En => words_en(time.hour(), minute),
+        WordClockLang::Ja => words_ja(time.hour(), minute),
+    }
+}
+
+// the dots of the minutes after the five minutes of the words. 0 .. 4. always 0 for the resolution 1
+pub fn word_clock_dots(time: &NaiveDateTime, resolution: u32) -> u32
+{
+    if resolution == 1 { 0 } else { time.minute() % 5 }
+}
+
+// the index of HOURS. 0 and 12 = h12
+fn hour12_index(hour: u32) -> usize
+{
+    match hour % 12
+    {
+        0 => 11,
+        x => x as usize - 1,
+    }
+}
+
+fn words_en(hour: u32, minute: u32) -> Vec<&'static str>
+{
+    let mut ret = vec!["it", "is"];
+
+    if minute == 0
+    {
+        ret.push(HOURS[hour12_index(hour)]);
+        ret.push("oclock");
+        return ret;
+    }
+
+    let (count, hour) = if minute <= 30 { (minute, hour) } else { (60 - minute, hour + 1) };
+
+    match count
+    {
+        15 => ret.push("quarter"),
+        30 => ret.push("half"),
+        x =>
+        {
+            if x > 20
+            {
+                ret.push(EN_MINUTES[19]);
+                ret.push(EN_MINUTES[x as usize - 21]);
+            }
+            else
+            {
+                ret.push(EN_MINUTES[x as usize - 1]);
+            }
+
+            ret.push(if x == 1 { "minute" } else { "minutes" });
+        },
+    }
+
+    ret.push(if minute <= 30 { "past" } else { "to" });
+    ret.push(HOURS[hour12_index(hour)]);
+
+    ret
+}
+
+fn words_ja(hour: u32, minute: u32) -> Vec<&'static str>
+{
+    let mut ret = vec![if hour < 12 { "am" } else { "pm" }, HOURS[hour12_index(hour)], "ji"];
+
+    match minute
+    {
+        0 => ret.push("choudo"),
+        30 => ret.push("half"),
+        x =>
+        {
+            if x >= 10
+            {
+                ret.push(JA_TENS[x as usize / 10 - 1]);
+            }
+
+            if x % 10 != 0
+            {
+                ret.push(JA_UNITS[x as usize % 10 - 1]);
+            }
+
+            ret.push("fun");
+        },
+    }
+
+    ret
+}
@@ -0,0 +1,138 @@
+mod alarm_tests {
+
+    use svgclock_rs::alarm::*;
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use serde::{Deserialize, Serialize};
+
+    fn dt(d: u32, h: u32, m: u32) -> NaiveDateTime {
+        // 2025-09-01 is Monday
+        NaiveDate::from_ymd_opt(2025, 9, d).unwrap().and_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn alarm(h: u32, m: u32, weekdays: &[AlarmDay]) -> Alarm {
+        let mut ret = Alarm::new(NaiveTime::from_hms_opt(h, m, 0).unwrap());
+        ret.weekdays = weekdays.to_vec();
+        ret
+    }
+
+    #[test]
+    fn test_next_after() {
+        let a = alarm(7, 30, &[]);
+
+        assert_eq!(a.next_after(&dt(1, 6, 0)), Some(dt(1, 7, 30)));
+        assert_eq!(a.next_after(&dt(1, 7, 30)), Some(dt(2, 7, 30)));
+
+        // weekdays. Monday 08:00 -> Friday
+        let a = alarm(7, 30, &[AlarmDay::Fri]);
+
+        assert_eq!(a.next_after(&dt(1, 8, 0)), Some(dt(5, 7, 30)));
+        assert_eq!(a.next_after(&dt(5, 7, 31)), Some(dt(12, 7, 30)));
+
+        let mut a = alarm(7, 30, &[]);
+        a.enabled = false;
+
+        assert_eq!(a.next_after(&dt(1, 6, 0)), None);
+    }
+
+    #[test]
+    fn test_due_next() {
+        let alarms = vec![
+            alarm(7, 30, &[]),
+            alarm(7, 30, &[AlarmDay::Sat, AlarmDay::Sun]),
+            alarm(12, 0, &[]),
+        ];
+
+        assert_eq!(due_alarms(&alarms, &dt(1, 7, 29), &dt(1, 7, 30)), vec![0]);
+        assert_eq!(due_alarms(&alarms, &dt(6, 7, 29), &dt(6, 7, 30)), vec![0, 1]);
+        assert!(due_alarms(&alarms, &dt(1, 7, 30), &dt(1, 7, 31)).is_empty());
+
+        assert_eq!(next_alarm(&alarms, &dt(1, 8, 0)), Some((2, dt(1, 12, 0))));
+        assert_eq!(next_alarm(&alarms, &dt(1, 13, 0)), Some((0, dt(2, 7, 30))));
+        assert_eq!(next_alarm(&[], &dt(1, 13, 0)), None);
+    }
+
+    #[test]
+    fn test_ids() {
+        let mut alarms = vec![alarm(7, 0, &[]), alarm(8, 0, &[]), alarm(9, 0, &[])];
+        alarms[1].id = 5;
+        alarms[2].id = 5;
+
+        // the missing and the duplicated ids
+        assert_eq!(assign_alarm_ids(&mut alarms, 1), 8);
+        assert_eq!(alarms.iter().map(|x| x.id).collect::<Vec<_>>(), vec![6, 5, 7]);
+
+        // not reused after a removal
+        alarms.pop();
+        alarms.push(alarm(10, 0, &[]));
+        assert_eq!(assign_alarm_ids(&mut alarms, 8), 9);
+        assert_eq!(alarms[2].id, 8);
+    }
+
+    #[test]
+    fn test_snooze_reorder() {
+        let mut alarms = vec![alarm(7, 30, &[]), alarm(12, 0, &[])];
+        assign_alarm_ids(&mut alarms, 1);
+
+        let id_noon = alarms[1].id;
+        let mut snoozes = vec![(id_noon, dt(1, 12, 5))];
+
+        // the alarms are edited. the snooze follows the alarm, not the position
+        alarms.swap(0, 1);
+
+        assert!(fire_alarms(&alarms, &mut snoozes, &dt(1, 12, 3), &dt(1, 12, 4)).is_empty());
+        assert_eq!(fire_alarms(&alarms, &mut snoozes, &dt(1, 12, 4), &dt(1, 12, 5)), vec![id_noon]);
+        assert_eq!(alarm_by_id(&alarms, id_noon).unwrap().time, NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+        assert!(snoozes.is_empty());
+
+        // the snooze of a removed alarm fires nothing
+        let mut snoozes = vec![(id_noon, dt(1, 12, 5))];
+        alarms.remove(0);
+
+        assert!(fire_alarms(&alarms, &mut snoozes, &dt(1, 12, 4), &dt(1, 12, 5)).is_empty());
+        assert!(snoozes.is_empty());
+    }
+
+    #[test]
+    fn test_weekdays_text() {
+        assert_eq!(alarm(7, 0, &[]).weekdays_text(), "Every day");
+        assert_eq!(alarm(7, 0, &[AlarmDay::Fri, AlarmDay::Mon]).weekdays_text(), "Mon Fri");
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Config {
+        alarms: Vec<Alarm>,
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let config: Config = toml::from_str(
+            r#"
+            [[alarms]]
+            time = "7:05"
+            weekdays = ["mon", "fri"]
+            label = "Wake up"
+
+            [[alarms]]
+            time = "22:00"
+            enabled = false
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.alarms[0], {
+            let mut x = alarm(7, 5, &[AlarmDay::Mon, AlarmDay::Fri]);
+            x.label = String::from("Wake up");
+            x
+        });
+        assert!(!config.alarms[1].enabled);
+        assert!(config.alarms[1].weekdays.is_empty());
+
+        let text = toml::to_string(&config).unwrap();
+
+        assert!(text.contains("time = \"07:05\""));
+        assert_eq!(toml::from_str::<Config>("[[alarms]]\nid = 3\ntime = \"7:05\"").unwrap().alarms[0].id, 3);
+
+        assert!(toml::from_str::<Config>("[[alarms]]\ntime = \"25:00\"").is_err());
+        assert_eq!(parse_alarm_time(" 07:30:15 "), NaiveTime::from_hms_opt(7, 30, 15));
+    }
+}
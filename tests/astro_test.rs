@@ -0,0 +1,104 @@
+mod astro_tests {
+
+    use chrono::NaiveDateTime;
+    use svgclock_rs::astro::*;
+
+    fn dt(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn assert_near(a: f64, b: f64, e: f64) {
+        assert!((a - b).abs() < e, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_julian_day() {
+        assert_near(julian_day(&dt("2000-01-01 12:00")), 2451545.0, 1e-9);
+        assert_near(julian_day(&dt("1970-01-01 00:00")), 2440587.5, 1e-9);
+    }
+
+    #[test]
+    fn test_moon_phase() {
+        // new moon 2024-01-11 11:57 UTC
+        let m = moon_phase(&dt("2024-01-11 11:57"));
+        assert!(m.phase < 0.01 || m.phase > 0.99, "{:?}", m);
+        assert_near(m.illumination, 0.0, 0.01);
+        assert_eq!(m.name(), "New Moon");
+
+        // first quarter 2024-01-18 03:53 UTC
+        let m = moon_phase(&dt("2024-01-18 03:53"));
+        assert_near(m.phase, 0.25, 0.01);
+        assert_near(m.illumination, 0.5, 0.02);
+        assert_eq!(m.name(), "First Quarter");
+
+        // full moon 2024-01-25 17:54 UTC
+        let m = moon_phase(&dt("2024-01-25 17:54"));
+        assert_near(m.phase, 0.5, 0.01);
+        assert_near(m.illumination, 1.0, 0.01);
+        assert_near(m.age, 14.77, 0.3);
+        assert_eq!(m.name(), "Full Moon");
+
+        // last quarter 2024-02-02 23:18 UTC
+        let m = moon_phase(&dt("2024-02-02 23:18"));
+        assert_near(m.phase, 0.75, 0.01);
+        assert_eq!(m.name(), "Last Quarter");
+
+        // waxing crescent
+        let m = moon_phase(&dt("2024-01-14 12:00"));
+        assert_eq!(m.name(), "Waxing Crescent");
+    }
+
+    fn date(s: &str) -> chrono::NaiveDate {
+        chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn assert_time(a: NaiveDateTime, b: &str) {
+        let b = dt(b);
+        assert!((a - b).num_seconds().abs() <= 180, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_sun_rise_set() {
+        // London 2024-06-21. 04:43 BST, 21:21 BST
+        match sun_rise_set(&date("2024-06-21"), 51.5074, -0.1278, SUN_ALTITUDE_SUNRISE) {
+            SunRiseSet::Normal(rise, set) => {
+                assert_time(rise, "2024-06-21 03:43");
+                assert_time(set, "2024-06-21 20:21");
+            }
+            x => panic!("{:?}", x),
+        }
+
+        // Tokyo 2024-03-20. 05:45 JST, 17:53 JST, noon 11:49 JST
+        match sun_rise_set(&date("2024-03-20"), 35.6895, 139.6917, SUN_ALTITUDE_SUNRISE) {
+            SunRiseSet::Normal(rise, set) => {
+                assert_time(rise, "2024-03-19 20:45");
+                assert_time(set, "2024-03-20 08:53");
+            }
+            x => panic!("{:?}", x),
+        }
+
+        assert_time(solar_noon(&date("2024-03-20"), 139.6917), "2024-03-20 02:49");
+
+        // civil twilight, London 2024-12-21. 07:25 GMT, 16:34 GMT
+        match sun_rise_set(&date("2024-12-21"), 51.5074, -0.1278, SUN_ALTITUDE_CIVIL) {
+            SunRiseSet::Normal(dawn, dusk) => {
+                assert_time(dawn, "2024-12-21 07:25");
+                assert_time(dusk, "2024-12-21 16:34");
+            }
+            x => panic!("{:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_sun_polar() {
+        // Tromso
+        assert_eq!(
+            sun_rise_set(&date("2024-06-21"), 69.6492, 18.9553, SUN_ALTITUDE_SUNRISE),
+            SunRiseSet::AlwaysAbove
+        );
+        assert_eq!(
+            sun_rise_set(&date("2024-12-21"), 69.6492, 18.9553, SUN_ALTITUDE_SUNRISE),
+            SunRiseSet::AlwaysBelow
+        );
+    }
+}
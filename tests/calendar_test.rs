@@ -0,0 +1,57 @@
+mod calendar_tests {
+
+    use svgclock_rs::calendar::*;
+    use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+
+    fn dt(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, mo, d).unwrap().and_hms_opt(h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_var() {
+        // Sunday
+        let c = Calendar::new(&dt(2025, 10, 5, 19, 7), TimeDelta::hours(9), "Asia/Tokyo");
+
+        assert_eq!(c.var("year").unwrap(), "2025");
+        assert_eq!(c.var("month").unwrap(), "10");
+        assert_eq!(c.var("day").unwrap(), "05");
+        assert_eq!(c.var("weekday").unwrap(), "7");
+        assert_eq!(c.var("month_name").unwrap(), "October");
+        assert_eq!(c.var("month_abbr").unwrap(), "Oct");
+        assert_eq!(c.var("weekday_name").unwrap(), "Sunday");
+        assert_eq!(c.var("weekday_abbr").unwrap(), "Sun");
+        assert_eq!(c.var("day_of_year").unwrap(), "278");
+        assert_eq!(c.var("iso_week").unwrap(), "40");
+        assert_eq!(c.var("ampm").unwrap(), "PM");
+        assert_eq!(c.var("hour12").unwrap(), "07");
+        assert_eq!(c.var("hour24").unwrap(), "19");
+        assert_eq!(c.var("utc_offset").unwrap(), "+09:00");
+        assert_eq!(c.var("tz_abbr").unwrap(), "JST");
+        assert_eq!(c.var("time"), None);
+
+        assert!(CALENDAR_VARS.iter().all(|x| c.var(x).is_some()));
+
+        // the ISO week belongs to the previous year
+        let c = Calendar::new(&dt(2021, 1, 1, 0, 0), TimeDelta::zero(), "UTC");
+
+        assert_eq!(c.var("iso_week").unwrap(), "53");
+        assert_eq!(c.var("iso_year").unwrap(), "2020");
+        assert_eq!(c.var("ampm").unwrap(), "AM");
+        assert_eq!(c.var("hour12").unwrap(), "12");
+    }
+
+    #[test]
+    fn test_time_zone_abbr() {
+        // summer time
+        let summer = dt(2025, 7, 1, 12, 0);
+        let winter = dt(2025, 1, 1, 12, 0);
+
+        assert_eq!(time_zone_abbr("Europe/London", &summer, TimeDelta::hours(1)), "BST");
+        assert_eq!(time_zone_abbr("Europe/London", &winter, TimeDelta::zero()), "GMT");
+        assert_eq!(time_zone_abbr("America/New_York", &winter, TimeDelta::hours(-5)), "EST");
+        assert_eq!(time_zone_abbr("GMT+9", &winter, TimeDelta::hours(9)), "GMT+9");
+        assert_eq!(time_zone_abbr("", &winter, TimeDelta::minutes(-330)), "-05:30");
+
+        assert_eq!(format_utc_offset(TimeDelta::minutes(345)), "+05:45");
+    }
+}
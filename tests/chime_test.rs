@@ -0,0 +1,86 @@
+mod chime_tests {
+
+    use svgclock_rs::chime::*;
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+
+    fn dt(h: u32, m: u32, s: u32, ms: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 9, 1).unwrap().and_hms_milli_opt(h, m, s, ms).unwrap()
+    }
+
+    fn hm(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_chime_between() {
+        let c = chime_between(&dt(13, 59, 59, 950), &dt(14, 0, 0, 50)).unwrap();
+
+        assert_eq!(c.kind, ChimeKind::Hour);
+        assert_eq!(c.time, dt(14, 0, 0, 0));
+        assert_eq!(c.hour12(), 2);
+
+        assert_eq!(chime_between(&dt(9, 29, 59, 900), &dt(9, 30, 0, 0)).unwrap().kind, ChimeKind::HalfHour);
+        assert_eq!(chime_between(&dt(9, 44, 59, 900), &dt(9, 45, 0, 0)).unwrap().kind, ChimeKind::QuarterHour);
+
+        // not crossed
+        assert!(chime_between(&dt(14, 0, 0, 0), &dt(14, 0, 0, 100)).is_none());
+        assert!(chime_between(&dt(14, 0, 1, 0), &dt(14, 0, 2, 0)).is_none());
+        assert!(chime_between(&dt(14, 0, 2, 0), &dt(13, 59, 59, 0)).is_none());
+
+        // midnight
+        let from = dt(23, 59, 59, 900);
+        let c = chime_between(&from, &(from + TimeDelta::milliseconds(200))).unwrap();
+
+        assert_eq!(c.time.time(), hm(0, 0));
+        assert_eq!(c.hour12(), 12);
+    }
+
+    #[test]
+    fn test_check() {
+        let mut config = ChimeConfig::new();
+
+        config.hour = true;
+        config.quiet_start = Some(hm(22, 0));
+        config.quiet_end = Some(hm(7, 0));
+
+        let gap = TimeDelta::minutes(1);
+
+        assert!(config.check(&dt(13, 59, 59, 900), &dt(14, 0, 0, 0), gap).is_some());
+        assert!(config.check(&dt(13, 29, 59, 900), &dt(13, 30, 0, 0), gap).is_none());
+        assert!(config.check(&dt(22, 59, 59, 900), &dt(23, 0, 0, 0), gap).is_none());
+        assert!(config.check(&dt(6, 59, 59, 900), &dt(7, 0, 0, 0), gap).is_some());
+
+        // a jump
+        assert!(config.check(&dt(13, 0, 1, 0), &dt(14, 0, 0, 0), gap).is_none());
+
+        config.quiet_start = Some(hm(12, 0));
+        config.quiet_end = Some(hm(13, 0));
+
+        assert!(config.is_quiet(&hm(12, 30)));
+        assert!(!config.is_quiet(&hm(13, 0)));
+        assert!(!config.is_quiet(&hm(22, 0)));
+    }
+
+    #[test]
+    fn test_expand_command() {
+        let c = chime_between(&dt(14, 59, 59, 0), &dt(15, 0, 0, 0)).unwrap();
+
+        assert_eq!(c.expand_command("strike.sh {hour12} {hour} {minute} {kind}"), "strike.sh 3 15 0 hour");
+        assert!(c.vars().iter().any(|x| x.0 == "SVGCLOCK_CHIME_HOUR12" && x.2 == "3"));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let config: ChimeConfig = toml::from_str("hour = true\nquiet_start = \"22:00\"\nquiet_end = \"07:00\"").unwrap();
+
+        assert!(config.hour && !config.half_hour && !config.notify);
+        assert_eq!(config.quiet_end, Some(hm(7, 0)));
+        assert_eq!(toml::from_str::<ChimeConfig>(&toml::to_string(&config).unwrap()).unwrap(), config);
+        assert!(toml::from_str::<ChimeConfig>("quiet_start = \"7\"").is_err());
+
+        // the same as the dialog
+        assert_eq!(parse_quiet_time(" 22:00 "), Some(hm(22, 0)));
+        assert_eq!(parse_quiet_time("22:00:30"), None);
+        assert!(toml::from_str::<ChimeConfig>("quiet_start = \"22:00:30\"").is_err());
+    }
+}
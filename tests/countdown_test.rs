@@ -0,0 +1,99 @@
+mod countdown_tests {
+
+    use svgclock_rs::countdown::*;
+    use chrono::{DateTime, TimeDelta, Utc};
+    use std::time::Duration;
+
+    fn t0() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_750_000_000, 0).unwrap()
+    }
+
+    #[test]
+    fn test_countdown() {
+        let mut cd = Countdown::new();
+
+        assert!(!cd.is_running());
+        assert_eq!(cd.remaining(&t0()), None);
+        assert!(!cd.check_expired(&t0()));
+
+        cd.start(&t0(), Duration::from_secs(300));
+
+        let t = t0() + TimeDelta::seconds(60);
+
+        assert!(cd.is_running());
+        assert_eq!(cd.remaining(&t), Some(Duration::from_secs(240)));
+        assert!((cd.fraction(&t) - 0.8).abs() < 1e-9);
+        assert!(!cd.check_expired(&t));
+
+        let t = t0() + TimeDelta::seconds(301);
+
+        assert_eq!(cd.remaining(&t), Some(Duration::ZERO));
+        assert!(cd.check_expired(&t));
+        assert!(!cd.check_expired(&t));
+        assert!(!cd.is_running());
+        assert_eq!(cd.duration(), Duration::from_secs(300));
+
+        cd.start(&t0(), Duration::from_secs(60));
+        cd.cancel();
+        assert!(!cd.check_expired(&(t0() + TimeDelta::seconds(120))));
+    }
+
+    #[test]
+    fn test_serialize() {
+        let mut cd = Countdown::new();
+
+        cd.start(&t0(), Duration::from_secs(90));
+
+        let text = toml::to_string(&cd).unwrap();
+        let cd2: Countdown = toml::from_str(&text).unwrap();
+
+        assert_eq!(cd, cd2);
+
+        let cd3: Countdown = toml::from_str("duration = 0").unwrap();
+
+        assert!(!cd3.is_running());
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::ZERO), "00:00");
+        assert_eq!(format_remaining(Duration::from_millis(299_001)), "05:00");
+        assert_eq!(format_remaining(Duration::from_secs(5400)), "1:30:00");
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("25"), Some(Duration::from_secs(25 * 60)));
+        assert_eq!(parse_duration(" 4:30 "), Some(Duration::from_secs(270)));
+        assert_eq!(parse_duration("1:30:00"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("2m 30s"), Some(Duration::from_secs(150)));
+        assert_eq!(parse_duration("90S"), Some(Duration::from_secs(90)));
+
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("0"), None);
+        assert_eq!(parse_duration("4:60"), None);
+        assert_eq!(parse_duration("10x"), None);
+        assert_eq!(parse_duration("1h30"), None);
+    }
+
+    #[test]
+    fn test_parse_duration_overflow() {
+        assert_eq!(parse_duration("999999999999999999"), None);
+        assert_eq!(parse_duration("999999999999999999:00"), None);
+        assert_eq!(parse_duration("999999999999999999:00:00"), None);
+        assert_eq!(parse_duration("999999999999999999h"), None);
+        assert_eq!(parse_duration("5000000000000000000s 5000000000000000000s"), None);
+
+        // over COUNTDOWN_MAX ( 100 hours )
+        assert_eq!(parse_duration("100h"), Some(COUNTDOWN_MAX));
+        assert_eq!(parse_duration("100h1s"), None);
+
+        let mut cd = Countdown::new();
+
+        cd.start(&t0(), Duration::from_secs(u64::MAX));
+
+        assert_eq!(cd.duration(), COUNTDOWN_MAX);
+        assert_eq!(cd.remaining(&t0()), Some(COUNTDOWN_MAX));
+    }
+}
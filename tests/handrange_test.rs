@@ -0,0 +1,106 @@
+mod handrange_tests {
+
+    use svgclock_rs::handrange::*;
+
+    fn assert_f64(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+    }
+
+    fn retrograde() -> HandRange {
+        HandRange {
+            start_angle: Some(-120.0),
+            end_angle: Some(120.0),
+            value_max: Some(6.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_full_turn() {
+        let r = HandRange::default();
+
+        assert!(r.is_full_turn());
+        assert_f64(r.angle(0.0, 24.0), 0.0);
+        assert_f64(r.angle(6.0, 24.0), 90.0);
+        assert_f64(r.angle(18.0, 24.0), 270.0);
+
+        // no snap back
+        assert_f64(r.angle_snap_back(0.0, 24.0, 0.1), 0.0);
+    }
+
+    #[test]
+    fn test_retrograde() {
+        let r = retrograde();
+
+        assert!(!r.is_full_turn());
+        assert_f64(r.angle(0.0, 7.0), -120.0);
+        assert_f64(r.angle(3.0, 7.0), 0.0);
+        assert_f64(r.angle(6.0, 7.0), 120.0);
+
+        // clamped
+        assert_f64(r.angle(6.5, 7.0), 120.0);
+        assert_f64(r.angle(-1.0, 7.0), -120.0);
+    }
+
+    #[test]
+    fn test_snap_back() {
+        let r = retrograde();
+
+        assert_f64(r.angle_snap_back(0.0, 7.0, 0.0), 120.0);
+        assert_f64(r.angle_snap_back(0.0, 7.0, 0.5), 0.0);
+        assert_f64(r.angle_snap_back(0.0, 7.0, 1.0), -120.0);
+        assert_f64(r.angle_snap_back(3.0, 7.0, 3.0 * 86400.0), 0.0);
+
+        let r = HandRange {
+            snap_back: Some(0.0),
+            ..retrograde()
+        };
+        assert_f64(r.angle_snap_back(0.0, 7.0, 0.0), -120.0);
+    }
+
+    #[test]
+    fn test_curve() {
+        let r = HandRange {
+            end_angle: Some(90.0),
+            value_max: Some(10.0),
+            curve: Some(HandCurve::QuadIn),
+            ..Default::default()
+        };
+
+        assert_f64(r.angle(5.0, 24.0), 22.5);
+        assert_f64(HandCurve::QuadOut.apply(0.5), 0.75);
+        assert_f64(HandCurve::SineInOut.apply(0.5), 0.5);
+        assert_f64(HandCurve::Linear.apply(0.3), 0.3);
+    }
+
+    #[test]
+    fn test_points() {
+        let r = HandRange {
+            points: Some(vec![(0.0, 0.0), (12.0, 60.0), (24.0, 300.0)]),
+            ..Default::default()
+        };
+
+        assert!(!r.is_full_turn());
+        assert_f64(r.angle(6.0, 24.0), 30.0);
+        assert_f64(r.angle(18.0, 24.0), 180.0);
+        assert_f64(r.angle(30.0, 24.0), 300.0);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let r: HandRange = toml::from_str(
+            r#"
+            start_angle = -120
+            end_angle = 120.0
+            curve = "cubic_in_out"
+            points = [[0, 0], [1, 90]]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(r.start_angle, Some(-120.0));
+        assert_eq!(r.curve, Some(HandCurve::CubicInOut));
+        assert_eq!(r.points, Some(vec![(0.0, 0.0), (1.0, 90.0)]));
+        assert_eq!(r.value_max, None);
+    }
+}
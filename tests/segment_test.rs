@@ -0,0 +1,108 @@
+mod segment_tests {
+
+    use svgclock_rs::segment::*;
+
+    #[test]
+    fn test_seven_segment() {
+        assert_eq!(seven_segment('0'), 0x7e);
+        assert_eq!(seven_segment('8'), 0x7f);
+        assert_eq!(seven_segment('9'), 0x7b);
+
+        // the other case
+        assert_eq!(seven_segment('B'), seven_segment('b'));
+        assert_eq!(seven_segment('a'), seven_segment('A'));
+        assert_ne!(seven_segment('C'), seven_segment('c'));
+
+        // unknown
+        assert_eq!(seven_segment('M'), 0x00);
+        assert_eq!(seven_segment('あ'), 0x00);
+    }
+
+    #[test]
+    fn test_seven_segment_is_on() {
+        // 1 = b, c
+        let on: String = SEVEN_SEGMENT_NAMES.iter().filter(|x| seven_segment_is_on('1', **x)).collect();
+
+        assert_eq!(on, "bc");
+
+        // 7 = a, b, c
+        assert!(seven_segment_is_on('7', 'A'));
+        assert!(!seven_segment_is_on('7', 'g'));
+
+        // - = g
+        assert!(seven_segment_is_on('-', 'g'));
+        assert!(!seven_segment_is_on('-', 'x'));
+    }
+
+    #[test]
+    fn test_sixteen_segment() {
+        // T = a1, a2, i, l
+        assert_eq!(sixteen_segment('T'), 0xc000 | 0x0010 | 0x0002);
+        assert_eq!(sixteen_segment('t'), sixteen_segment('T'));
+
+        // unknown
+        assert_eq!(sixteen_segment('あ'), 0x0000);
+
+        // every segment of the table is a known name
+        for c in ('A'..='Z').chain('0'..='9') {
+            assert_ne!(sixteen_segment(c), 0x0000, "{}", c);
+        }
+    }
+
+    #[test]
+    fn test_sixteen_segment_is_on() {
+        // M = b, c, e, f, h, j
+        let on: Vec<&str> =
+            SIXTEEN_SEGMENT_NAMES.iter().copied().filter(|x| sixteen_segment_is_on('M', x)).collect();
+
+        assert_eq!(on, vec!["b", "c", "e", "f", "h", "j"]);
+
+        // fourteen segments
+        assert!(sixteen_segment_is_on('O', "a"));
+        assert!(sixteen_segment_is_on('O', "D"));
+        assert!(!sixteen_segment_is_on('N', "a"));
+        assert!(!sixteen_segment_is_on('N', "x"));
+    }
+
+    #[test]
+    fn test_dot_matrix_is_on() {
+        // T
+        for col in 0..DOT_MATRIX_COLS {
+            assert!(dot_matrix_is_on('T', 0, col));
+        }
+        assert!(dot_matrix_is_on('t', 6, 2));
+        assert!(!dot_matrix_is_on('T', 6, 0));
+
+        // out of range, unknown
+        assert!(!dot_matrix_is_on('T', DOT_MATRIX_ROWS, 0));
+        assert!(!dot_matrix_is_on('T', 0, DOT_MATRIX_COLS));
+        assert!(!dot_matrix_is_on('あ', 0, 0));
+
+        for c in ('A'..='Z').chain('0'..='9') {
+            assert!((0..DOT_MATRIX_ROWS).any(|row| (0..DOT_MATRIX_COLS).any(|col| dot_matrix_is_on(c, row, col))), "{}", c);
+        }
+    }
+
+    #[test]
+    fn test_digit() {
+        use chrono::NaiveDate;
+
+        let time = NaiveDate::from_ymd_opt(2025, 2, 14).unwrap().and_hms_opt(19, 7, 36).unwrap();
+
+        let digits: Vec<u32> = DIGIT_POSITIONS.iter().filter_map(|x| digit(&time, x, false)).collect();
+
+        assert_eq!(digits, vec![1, 9, 0, 7, 3, 6, 2, 0, 2, 5, 0, 2, 1, 4, 0, 4, 5]);
+
+        // 12 hour
+        assert_eq!(digit(&time, "hh", true), Some(0));
+        assert_eq!(digit(&time, "HL", true), Some(7));
+
+        let midnight = NaiveDate::from_ymd_opt(2025, 2, 14).unwrap().and_hms_opt(0, 0, 0).unwrap();
+
+        assert_eq!(digit(&midnight, "hh", true), Some(1));
+        assert_eq!(digit(&midnight, "hl", true), Some(2));
+
+        // unknown
+        assert_eq!(digit(&time, "xx", false), None);
+    }
+}
@@ -0,0 +1,83 @@
+mod stopwatch_tests {
+
+    use svgclock_rs::stopwatch::*;
+    use std::time::{Duration, Instant};
+
+    fn ms(x: u64) -> Duration {
+        Duration::from_millis(x)
+    }
+
+    #[test]
+    fn test_start_stop() {
+        let t0 = Instant::now();
+        let mut sw = Stopwatch::new();
+
+        assert!(sw.is_reset());
+        assert_eq!(sw.elapsed(t0 + ms(500)), Duration::ZERO);
+
+        sw.start(t0);
+        assert!(sw.is_running());
+        assert_eq!(sw.elapsed(t0 + ms(1500)), ms(1500));
+
+        sw.stop(t0 + ms(2000));
+        assert!(!sw.is_running());
+        assert_eq!(sw.elapsed(t0 + ms(9000)), ms(2000));
+
+        // accumulated
+        sw.toggle(t0 + ms(5000));
+        assert_eq!(sw.elapsed(t0 + ms(5250)), ms(2250));
+        sw.toggle(t0 + ms(6000));
+        assert_eq!(sw.elapsed(t0 + ms(9000)), ms(3000));
+    }
+
+    #[test]
+    fn test_lap_reset() {
+        let t0 = Instant::now();
+        let mut sw = Stopwatch::new();
+
+        sw.lap(t0);
+        assert!(sw.laps().is_empty());
+
+        sw.start(t0);
+        sw.lap(t0 + ms(1000));
+        assert_eq!(sw.last_lap(), Some(ms(1000)));
+        sw.lap(t0 + ms(2500));
+        assert_eq!(sw.laps(), &[ms(1000), ms(2500)]);
+        assert_eq!(sw.last_lap(), Some(ms(1500)));
+
+        // not while running
+        sw.reset();
+        assert_eq!(sw.laps().len(), 2);
+
+        sw.stop(t0 + ms(3000));
+        assert!(!sw.is_reset());
+        sw.reset();
+        assert!(sw.is_reset());
+        assert_eq!(sw.last_lap(), None);
+        assert_eq!(sw.elapsed(t0 + ms(4000)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::ZERO), "00:00:00.0");
+        assert_eq!(format_elapsed(ms(83_456)), "00:01:23.4");
+        assert_eq!(format_elapsed(ms(36_000_000 + 61_000)), "10:01:01.0");
+    }
+
+    #[test]
+    fn test_driver() {
+        let d = ms(95_500); // 1:35.5
+
+        assert_eq!(ChronoDriver::ChronoSecond.value(d, false), 35.0);
+        assert!((ChronoDriver::ChronoSecond.value(d, true) - 35.5).abs() < 1e-9);
+        assert_eq!(ChronoDriver::ChronoMinute.value(d, false), 1.0);
+
+        let d = ms(45 * 60 * 1000);
+
+        assert_eq!(ChronoDriver::ChronoMinute30.value(d, false), 15.0);
+        assert!((ChronoDriver::ChronoHour12.value(d, true) - 0.75).abs() < 1e-9);
+
+        assert_eq!(ChronoDriver::ChronoMinute30.range(), 30.0);
+        assert_eq!(ChronoDriver::ChronoHour12.unit_secs(), 3600.0);
+    }
+}
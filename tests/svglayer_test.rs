@@ -0,0 +1,73 @@
+mod svglayer_tests {
+
+    use svgclock_rs::svglayer::*;
+
+    fn known() -> Vec<String> {
+        ["base", "second_handle", "center_circle", "date_wheel"].iter().map(|x| x.to_string()).collect()
+    }
+
+    fn filter(src: &str, name: &str) -> Option<String> {
+        filter_xml_layer(src.as_bytes(), name, &known()).unwrap().map(|x| String::from_utf8(x).unwrap())
+    }
+
+    const SVG_NESTED: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape">
+<defs><linearGradient id="g1"/></defs>
+<g inkscape:groupmode="layer" inkscape:label="base">
+<circle r="50"/>
+<g inkscape:groupmode="layer" inkscape:label="ticks"><path d="M0 0"/></g>
+<g inkscape:groupmode="layer" inkscape:label="second_handle"><path id="second_path" d="M0 0"/></g>
+<g id="date_wheel"><path id="wheel_path" d="M0 0"/></g>
+</g>
+</svg>"##;
+
+    #[test]
+    fn test_nested_layer() {
+        // the nested layers are drawn by themselves, not in the parent
+        let base = filter(SVG_NESTED, "base").unwrap();
+
+        assert!(base.contains("<circle"));
+        assert!(base.contains("ticks"));
+        assert!(base.contains("linearGradient"));
+        assert!(!base.contains("second_handle"));
+        assert!(!base.contains("second_path"));
+        assert!(!base.contains("wheel_path"));
+
+        let second_handle = filter(SVG_NESTED, "second_handle").unwrap();
+
+        assert!(second_handle.contains("second_path"));
+        assert!(second_handle.contains("linearGradient"));
+        assert!(!second_handle.contains("<circle"));
+        assert!(!second_handle.contains("ticks"));
+
+        let date_wheel = filter(SVG_NESTED, "date_wheel").unwrap();
+
+        assert!(date_wheel.contains("wheel_path"));
+        assert!(!date_wheel.contains("second_path"));
+
+        assert!(filter(SVG_NESTED, "long_handle").is_none());
+    }
+
+    const SVG_NS: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:sc="https://github.com/zuntan/svgclock-rs/" xmlns:svgclock="http://example.com/other">
+<style>.a{}</style>
+<rect id="stray_rect"/>
+<g sc:layer="second_handle"><path id="second_path"/></g>
+<g svgclock:layer="long_handle"><path id="long_path"/></g>
+</svg>"##;
+
+    #[test]
+    fn test_namespace() {
+        assert_eq!(get_layer_ns_keys(SVG_NS.as_bytes()), vec!["sc:layer"]);
+
+        let second_handle = filter(SVG_NS, "second_handle").unwrap();
+
+        assert!(second_handle.contains("second_path"));
+        assert!(second_handle.contains("<style>"));
+
+        // the other elements at the root are not copied into the layers
+        assert!(!second_handle.contains("stray_rect"));
+        assert!(!second_handle.contains("long_path"));
+
+        // the prefix bound to the other namespace
+        assert!(filter(SVG_NS, "long_handle").is_none());
+    }
+}
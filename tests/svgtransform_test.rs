@@ -0,0 +1,249 @@
+mod svgtransform_tests {
+
+    use svgclock_rs::svgtransform::*;
+    use glam::{DAffine2, DVec2};
+    use quick_xml::events::BytesStart;
+
+    fn assert_vec2(a: DVec2, b: DVec2) {
+        assert!((a - b).length() < 1e-6, "{:?} != {:?}", a, b);
+    }
+
+    fn apply(transform: &str, x: f64, y: f64) -> DVec2 {
+        parse_svg_transform(transform).unwrap().transform_point2(DVec2::new(x, y))
+    }
+
+    fn is_layer(tag: &BytesStart<'_>, label: &str) -> bool {
+        tag.name().as_ref() == b"g"
+            && matches!(
+                tag.try_get_attribute("inkscape:label"),
+                Ok(Some(attr)) if attr.value.as_ref() == label.as_bytes()
+            )
+    }
+
+    #[test]
+    fn test_float_list() {
+        assert_eq!(parse_float_list("1,2 3"), vec![1.0, 2.0, 3.0]);
+        assert_eq!(parse_float_list("-1.5e2-.5"), vec![-150.0, -0.5]);
+        assert_eq!(parse_float_list("1.5.5"), vec![1.5, 0.5]);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(parse_svg_transform_value("").is_none());
+        assert!(parse_svg_transform_value("  \n ").is_none());
+        assert_eq!(parse_svg_transform("").unwrap(), DAffine2::IDENTITY);
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_vec2(apply("translate(10,20)", 1.0, 2.0), DVec2::new(11.0, 22.0));
+        assert_vec2(apply("translate(10)", 1.0, 2.0), DVec2::new(11.0, 2.0));
+        assert_vec2(apply("translate( 10 20 )", 1.0, 2.0), DVec2::new(11.0, 22.0));
+        assert_vec2(apply("translate(10-20)", 1.0, 2.0), DVec2::new(11.0, -18.0));
+    }
+
+    #[test]
+    fn test_scale() {
+        assert_vec2(apply("scale(2)", 3.0, 4.0), DVec2::new(6.0, 8.0));
+        assert_vec2(apply("scale(2,0.5)", 3.0, 4.0), DVec2::new(6.0, 2.0));
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_vec2(apply("rotate(90)", 1.0, 0.0), DVec2::new(0.0, 1.0));
+        assert_vec2(apply("rotate(180,50,50)", 60.0, 50.0), DVec2::new(40.0, 50.0));
+        assert_vec2(apply("rotate(-90 10 10)", 20.0, 10.0), DVec2::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn test_skew() {
+        assert_vec2(apply("skewX(45)", 0.0, 10.0), DVec2::new(10.0, 10.0));
+        assert_vec2(apply("skewY(45)", 10.0, 0.0), DVec2::new(10.0, 10.0));
+    }
+
+    #[test]
+    fn test_matrix() {
+        assert_vec2(apply("matrix(1,0,0,1,5,6)", 1.0, 1.0), DVec2::new(6.0, 7.0));
+        assert_vec2(apply("matrix(0 1 -1 0 0 0)", 1.0, 0.0), DVec2::new(0.0, 1.0));
+        assert_vec2(
+            apply("matrix(0.70710678,0.70710678,-0.70710678,0.70710678,35.355339,-14.644661)", 50.0, 50.0),
+            DVec2::new(35.355339, 56.066017),
+        );
+    }
+
+    #[test]
+    fn test_list() {
+        // applied from right to left
+        assert_vec2(apply("translate(10,0) scale(2)", 1.0, 1.0), DVec2::new(12.0, 2.0));
+        assert_vec2(apply("scale(2),translate(10,0)", 1.0, 1.0), DVec2::new(22.0, 2.0));
+        assert_vec2(apply("translate(10,0)scale(2)", 1.0, 1.0), DVec2::new(12.0, 2.0));
+        assert_vec2(apply(" \n translate(1e1 , 0) ,\n rotate(90) ", 1.0, 0.0), DVec2::new(10.0, 1.0));
+    }
+
+    #[test]
+    fn test_error() {
+        assert!(parse_svg_transform("translate(1,2,3)").is_err());
+        assert!(parse_svg_transform("matrix(1,0,0)").is_err());
+        assert!(parse_svg_transform("rotate(1,2)").is_err());
+        assert!(parse_svg_transform("move(1,2)").is_err());
+        assert!(parse_svg_transform("scale(2").is_err());
+        assert!(parse_svg_transform_value("scale(2").is_none());
+    }
+
+    const SVG_INKSCAPE: &str = r##"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<!-- Created with Inkscape (http://www.inkscape.org/) -->
+<svg
+   width="200"
+   height="200"
+   viewBox="0 0 200 200"
+   version="1.1"
+   id="svg1"
+   inkscape:version="1.3.2 (091e20ef0f, 2023-11-25)"
+   xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape"
+   xmlns:sodipodi="http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd"
+   xmlns="http://www.w3.org/2000/svg"
+   xmlns:svg="http://www.w3.org/2000/svg">
+  <sodipodi:namedview
+     id="namedview1"
+     pagecolor="#ffffff"
+     inkscape:zoom="2.4" />
+  <defs
+     id="defs1" />
+  <g
+     inkscape:label="base"
+     inkscape:groupmode="layer"
+     id="layer1">
+    <circle
+       style="fill:#ffffff"
+       id="path1"
+       cx="50"
+       cy="50"
+       r="45" />
+  </g>
+  <g
+     inkscape:groupmode="layer"
+     id="layer2"
+     inkscape:label="center_circle"
+     transform="translate(-35.5,12.25)">
+    <g
+       id="g1"
+       transform="scale(2)">
+      <circle
+         style="fill:#000000"
+         id="path2"
+         cx="50"
+         cy="30"
+         r="2" />
+    </g>
+  </g>
+  <g
+     inkscape:groupmode="layer"
+     id="layer3"
+     inkscape:label="sub_second_center_circle"
+     transform="matrix(0.70710678,0.70710678,-0.70710678,0.70710678,35.355339,-14.644661)">
+    <ellipse
+       style="fill:#000000"
+       id="path3"
+       cx="50"
+       cy="50"
+       rx="2"
+       ry="2"
+       transform="translate(10,0)"></ellipse>
+  </g>
+  <g
+     inkscape:groupmode="layer"
+     id="layer4"
+     inkscape:label="old_center_circle">
+    <path
+       sodipodi:type="arc"
+       style="fill:#000000"
+       id="path4"
+       sodipodi:cx="100"
+       sodipodi:cy="120"
+       sodipodi:rx="2"
+       sodipodi:ry="2"
+       d="m 102,120 a 2,2 0 0 1 -2,2 2,2 0 0 1 -2,-2 2,2 0 0 1 2,-2 2,2 0 0 1 2,2 z"
+       transform="skewX(45)" />
+  </g>
+  <g
+     inkscape:groupmode="layer"
+     id="layer5"
+     inkscape:label="empty" />
+</svg>
+"##;
+
+    #[test]
+    fn test_center_group() {
+        let center = find_center(SVG_INKSCAPE.as_bytes(), |tag, depth| {
+            depth == 2 && is_layer(tag, "center_circle")
+        });
+        assert_vec2(center.unwrap().unwrap(), DVec2::new(64.5, 72.25));
+    }
+
+    #[test]
+    fn test_center_matrix() {
+        let center = find_center(SVG_INKSCAPE.as_bytes(), |tag, depth| {
+            depth == 2 && is_layer(tag, "sub_second_center_circle")
+        });
+
+        let m = parse_svg_transform("matrix(0.70710678,0.70710678,-0.70710678,0.70710678,35.355339,-14.644661)")
+            .unwrap();
+
+        assert_vec2(center.unwrap().unwrap(), m.transform_point2(DVec2::new(60.0, 50.0)));
+    }
+
+    #[test]
+    fn test_center_arc() {
+        let center = find_center(SVG_INKSCAPE.as_bytes(), |tag, depth| {
+            depth == 2 && is_layer(tag, "old_center_circle")
+        });
+        assert_vec2(center.unwrap().unwrap(), DVec2::new(220.0, 120.0));
+    }
+
+    #[test]
+    fn test_center_target_itself() {
+        // exported from Figma. the layer is the circle itself
+        let src = r##"<svg width="100" height="100" viewBox="0 0 100 100" fill="none" xmlns="http://www.w3.org/2000/svg">
+<g id="base"><rect width="100" height="100" fill="white"/></g>
+<circle id="center_circle" cx="50" cy="52" r="3" fill="black" transform="translate(1 -2)"/>
+</svg>
+"##;
+        let center = find_center(src.as_bytes(), |tag, depth| {
+            depth == 2
+                && matches!(
+                    tag.try_get_attribute("id"),
+                    Ok(Some(attr)) if attr.value.as_ref() == b"center_circle"
+                )
+        });
+        assert_vec2(center.unwrap().unwrap(), DVec2::new(51.0, 50.0));
+    }
+
+    #[test]
+    fn test_center_nested() {
+        // a sub-layer in a parent layer. the transform of the parent is applied
+        let src = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape">
+  <g inkscape:groupmode="layer" inkscape:label="hands" transform="translate(10,20)">
+    <circle cx="0" cy="0" r="1"/>
+    <g inkscape:groupmode="layer" inkscape:label="center_circle" transform="scale(2)">
+      <circle cx="5" cy="5" r="1"/>
+    </g>
+  </g>
+</svg>
+"##;
+        let center = find_center(src.as_bytes(), |tag, _| is_layer(tag, "center_circle"));
+        assert_vec2(center.unwrap().unwrap(), DVec2::new(20.0, 30.0));
+    }
+
+    #[test]
+    fn test_center_not_found() {
+        let center = find_center(SVG_INKSCAPE.as_bytes(), |tag, depth| {
+            depth == 2 && is_layer(tag, "empty")
+        });
+        assert!(center.unwrap().is_none());
+
+        let center = find_center(SVG_INKSCAPE.as_bytes(), |tag, depth| {
+            depth == 2 && is_layer(tag, "no_such_layer")
+        });
+        assert!(center.unwrap().is_none());
+    }
+}
@@ -0,0 +1,119 @@
+mod timedriver_tests {
+
+    use chrono::NaiveDateTime;
+    use svgclock_rs::timedriver::*;
+
+    fn dt(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").unwrap()
+    }
+
+    fn assert_f64(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_time() {
+        let t = dt("2025-03-10 18:30:15.5");
+
+        assert_f64(TimeDriver::Hour12.value(&t, false), 6.0);
+        assert_f64(TimeDriver::Hour12.value(&t, true), 6.0 + 30.0 / 60.0 + 15.5 / 3600.0);
+        assert_f64(TimeDriver::Hour24.fraction(&t, false), 18.0 / 24.0);
+        assert_f64(TimeDriver::MinuteOfHour.value(&t, true), 30.0 + 15.5 / 60.0);
+        assert_f64(TimeDriver::SecondOfMinute.value(&t, true), 15.5);
+        assert_f64(TimeDriver::SecondOfMinute.value(&t, false), 15.0);
+
+        let t = dt("2025-03-10 12:00:00.0");
+        assert_f64(TimeDriver::Hour12.value(&t, true), 0.0);
+    }
+
+    #[test]
+    fn test_date() {
+        // Monday
+        let t = dt("2025-03-10 12:00:00.0");
+
+        assert_f64(TimeDriver::DayOfWeek.value(&t, false), 1.0);
+        assert_f64(TimeDriver::DayOfWeek.value(&t, true), 1.5);
+        assert_f64(TimeDriver::DayOfMonth.value(&t, false), 9.0);
+        assert_f64(TimeDriver::DayOfMonth.fraction(&t, false), 9.0 / 31.0);
+        assert_f64(TimeDriver::Month.value(&t, false), 2.0);
+        assert_f64(TimeDriver::Month.value(&t, true), 2.0 + 9.5 / 31.0);
+
+        let t = dt("2025-02-15 00:00:00.0");
+        assert_f64(TimeDriver::Month.value(&t, true), 1.0 + 14.0 / 28.0);
+    }
+
+    #[test]
+    fn test_elapsed_secs() {
+        let day = 24.0 * 60.0 * 60.0;
+
+        let t = dt("2025-03-10 18:30:15.5");
+        assert_f64(TimeDriver::Hour12.elapsed_secs(&t), 6.0 * 3600.0 + 30.0 * 60.0 + 15.5);
+        assert_f64(TimeDriver::DayOfWeek.elapsed_secs(&t), day + 18.0 * 3600.0 + 30.0 * 60.0 + 15.5);
+
+        // counted from January 1st. not month0 * the days of the current month
+        let t = dt("2025-03-01 06:00:00.0");
+        assert_f64(TimeDriver::Month.elapsed_secs(&t), (31.0 + 28.0) * day + 6.0 * 3600.0);
+
+        let t = dt("2025-01-01 00:00:01.0");
+        assert_f64(TimeDriver::Month.elapsed_secs(&t), 1.0);
+    }
+
+    #[test]
+    fn test_year_progress() {
+        let t = dt("2025-01-01 00:00:00.0");
+        assert_f64(TimeDriver::YearProgress.fraction(&t, true), 0.0);
+
+        let t = dt("2025-12-31 12:00:00.0");
+        assert_f64(TimeDriver::YearProgress.range(&t), 365.0);
+        assert_f64(TimeDriver::YearProgress.value(&t, true), 364.5);
+
+        let t = dt("2024-12-31 00:00:00.0");
+        assert_f64(TimeDriver::YearProgress.range(&t), 366.0);
+        assert_f64(TimeDriver::YearProgress.value(&t, false), 365.0);
+    }
+
+    #[test]
+    fn test_smooth_default() {
+        assert!(TimeDriver::Hour24.is_smooth_default());
+        assert!(TimeDriver::YearProgress.is_smooth_default());
+        assert!(!TimeDriver::DayOfMonth.is_smooth_default());
+        assert!(!TimeDriver::DayOfWeek.is_smooth_default());
+    }
+
+    #[test]
+    fn test_step_position() {
+        let d = TimeDriver::DayOfMonth;
+
+        assert_f64(d.step_position(&dt("2025-03-10 12:00:00.0"), 1.0), 9.0);
+        assert_f64(d.step_position(&dt("2025-03-10 00:00:00.0"), 1.0), 8.0);
+        assert_f64(d.step_position(&dt("2025-03-10 00:00:00.5"), 1.0), 8.5);
+        assert_f64(d.step_position(&dt("2025-03-10 00:00:01.0"), 1.0), 9.0);
+        assert_f64(d.step_position(&dt("2025-03-10 00:00:00.5"), 0.0), 9.0);
+
+        // the end of April. 30th -> 1st
+        assert_f64(d.step_position(&dt("2025-05-01 00:00:00.5"), 1.0), 30.0);
+
+        // the end of a leap year
+        let d = TimeDriver::YearProgress;
+        assert_f64(d.step_position(&dt("2025-01-01 00:00:00.5"), 1.0), 365.5);
+
+        let d = TimeDriver::Month;
+        assert_f64(d.step_position(&dt("2025-01-01 00:00:00.25"), 1.0), 11.125);
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Hand {
+        driver: TimeDriver,
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let hand: Hand = toml::from_str(r#"driver = "day_of_week""#).unwrap();
+        assert_eq!(hand.driver, TimeDriver::DayOfWeek);
+
+        let hand: Hand = toml::from_str(r#"driver = "hour24""#).unwrap();
+        assert_eq!(hand.driver, TimeDriver::Hour24);
+
+        assert!(toml::from_str::<Hand>(r#"driver = "hour25""#).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+mod wordclock_tests {
+
+    use svgclock_rs::wordclock::*;
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    fn dt(h: u32, mi: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 10, 5).unwrap().and_hms_opt(h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_words_en() {
+        let en = WordClockLang::En;
+
+        assert_eq!(word_clock_words(en, &dt(10, 0), 5), vec!["it", "is", "h10", "oclock"]);
+        assert_eq!(word_clock_words(en, &dt(0, 2), 5), vec!["it", "is", "h12", "oclock"]);
+        assert_eq!(word_clock_words(en, &dt(10, 27), 5), vec!["it", "is", "m20", "m5", "minutes", "past", "h10"]);
+        assert_eq!(word_clock_words(en, &dt(22, 34), 5), vec!["it", "is", "half", "past", "h10"]);
+        assert_eq!(word_clock_words(en, &dt(10, 45), 5), vec!["it", "is", "quarter", "to", "h11"]);
+        assert_eq!(word_clock_words(en, &dt(23, 55), 5), vec!["it", "is", "m5", "minutes", "to", "h12"]);
+
+        // one minute
+        assert_eq!(word_clock_words(en, &dt(10, 1), 1), vec!["it", "is", "m1", "minute", "past", "h10"]);
+        assert_eq!(word_clock_words(en, &dt(10, 23), 1), vec!["it", "is", "m20", "m3", "minutes", "past", "h10"]);
+        assert_eq!(word_clock_words(en, &dt(10, 47), 1), vec!["it", "is", "m13", "minutes", "to", "h11"]);
+    }
+
+    #[test]
+    fn test_words_ja() {
+        let ja = WordClockLang::from_name("JA").unwrap();
+
+        assert_eq!(word_clock_words(ja, &dt(10, 0), 5), vec!["am", "h10", "ji", "choudo"]);
+        assert_eq!(word_clock_words(ja, &dt(12, 34), 5), vec!["pm", "h12", "ji", "half"]);
+        assert_eq!(word_clock_words(ja, &dt(22, 27), 5), vec!["pm", "h10", "ji", "t2", "u5", "fun"]);
+        assert_eq!(word_clock_words(ja, &dt(22, 40), 5), vec!["pm", "h10", "ji", "t4", "fun"]);
+
+        // one minute
+        assert_eq!(word_clock_words(ja, &dt(9, 7), 1), vec!["am", "h9", "ji", "u7", "fun"]);
+        assert_eq!(word_clock_words(ja, &dt(9, 59), 1), vec!["am", "h9", "ji", "t5", "u9", "fun"]);
+
+        assert_eq!(WordClockLang::from_name("fr"), None);
+    }
+
+    #[test]
+    fn test_dots() {
+        assert_eq!(word_clock_dots(&dt(10, 27), 5), 2);
+        assert_eq!(word_clock_dots(&dt(10, 25), 5), 0);
+        assert_eq!(word_clock_dots(&dt(10, 27), 1), 0);
+    }
+}